@@ -0,0 +1,59 @@
+use crate::bot::{Situation, Strategy};
+use crate::config::Config;
+use crate::console::input::PlayerInteractions;
+use crate::game::money::Money;
+use crate::player::ledger::Side;
+
+/// Greedy arbitrage, buys as much as it can of fruit priced in the lowest quarter of its price
+/// range and sells as soon as the store pays more than the average price paid for the fruit.
+/// Fruit left at the end of the game is worth nothing, so during the last
+/// Config::BOT_SELL_OFF_OFFERS offers it stops buying and sells whatever it holds at any price
+pub(crate) struct Arbitrage {}
+
+impl Arbitrage {
+    pub(crate) const NAME: &'static str = "arbitrage";
+
+    pub(crate) fn new() -> Arbitrage {
+        return Arbitrage {};
+    }
+
+    /// Average price paid for one of the offered fruit over all purchases of it, None if the fruit
+    /// was never bought
+    fn average_paid(situation :&Situation) -> Option<Money> {
+        let fruit = situation.offer.get_fruit();
+        let (paid, quantity) = situation.player.get_ledger().iter()
+            .filter(|transaction| transaction.side == Side::Buy && transaction.fruit == fruit)
+            .fold((0, 0), |(paid, quantity), transaction| {
+                (paid + transaction.price.get_cents(), quantity + transaction.quantity)
+            });
+        if quantity == 0 {
+            return None;
+        }
+        return Some(Money::from_cents(paid).per_unit(quantity));
+    }
+}
+
+impl Strategy for Arbitrage {
+    fn get_name(&self) -> &'static str {
+        return Arbitrage::NAME;
+    }
+
+    fn decide(&mut self, situation :&Situation) -> Option<PlayerInteractions> {
+        if situation.is_top_bidder() {
+            return None;
+        }
+        let selling_off = situation.offers_left < Config::BOT_SELL_OFF_OFFERS;
+        if let Some(sell_price) = situation.get_sell_price() {
+            let profitable = Arbitrage::average_paid(situation).is_none_or(|paid| sell_price > paid);
+            if profitable || selling_off {
+                return Some(PlayerInteractions::Sell);
+            }
+        }
+        let (start, end) = (situation.range.start().get_cents(), situation.range.end().get_cents());
+        let lowest_quarter = Money::from_cents(start + (end - start) / 4);
+        if !selling_off && situation.can_buy() && situation.get_unit_price() <= situation.get_resale_price(lowest_quarter) {
+            return Some(PlayerInteractions::Buy);
+        }
+        return Some(PlayerInteractions::NextOffer);
+    }
+}
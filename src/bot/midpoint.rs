@@ -0,0 +1,36 @@
+use crate::bot::{Situation, Strategy};
+use crate::console::input::PlayerInteractions;
+
+/// Buys fruit priced below the middle of its price range and sells fruit when its price is above
+/// the middle. Prices of the offered grade are compared against what the store would pay for the
+/// grade at the middle of the range, so premium fruit has to be cheaper to be bought. Bids on
+/// auctions while the price per fruit stays below the middle, skips every other offer
+pub(crate) struct Midpoint {}
+
+impl Midpoint {
+    pub(crate) const NAME: &'static str = "midpoint";
+
+    pub(crate) fn new() -> Midpoint {
+        return Midpoint {};
+    }
+}
+
+impl Strategy for Midpoint {
+    fn get_name(&self) -> &'static str {
+        return Midpoint::NAME;
+    }
+
+    fn decide(&mut self, situation :&Situation) -> Option<PlayerInteractions> {
+        if situation.is_top_bidder() {
+            return None;
+        }
+        let midpoint = situation.get_midpoint();
+        if situation.get_sell_price().is_some() && situation.offer.get_base_price() > midpoint {
+            return Some(PlayerInteractions::Sell);
+        }
+        if situation.can_buy() && situation.get_unit_price() < situation.get_resale_price(midpoint) {
+            return Some(PlayerInteractions::Buy);
+        }
+        return Some(PlayerInteractions::NextOffer);
+    }
+}
@@ -0,0 +1,232 @@
+pub mod midpoint;
+pub mod arbitrage;
+pub mod random;
+
+use std::ops::RangeInclusive;
+use strum::IntoEnumIterator;
+
+use crate::bot::arbitrage::Arbitrage;
+use crate::bot::midpoint::Midpoint;
+use crate::bot::random::Random;
+use crate::config::Config;
+use crate::console::input::PlayerInteractions;
+use crate::fruit::FruitOffer;
+use crate::fruit::auction::Auction;
+use crate::fruit::fruit_offer_controller::FruitOfferController;
+use crate::fruit::fruit_store::FruitStore;
+use crate::fruit::quality::Quality;
+use crate::game::error::GameError;
+use crate::game::money::Money;
+use crate::player::Player;
+
+/// Everything a strategy gets to see when deciding what to do with the current offer, the same the
+/// player sees on the screen and with the info key
+pub(crate) struct Situation<'a> {
+    pub(crate) offer: &'a FruitOffer,
+    /// Range the offered fruit's base price is picked from given the season and market event
+    pub(crate) range: RangeInclusive<Money>,
+    /// Bidding state when the offer is an auction
+    pub(crate) auction: Option<&'a Auction>,
+    /// Amount of fruit bought from the offer so far, for volume discounts
+    pub(crate) units_bought: u32,
+    pub(crate) player: &'a Player,
+    /// Offers following the current one before the game is over
+    pub(crate) offers_left: u32
+}
+
+impl<'a> Situation<'a> {
+    /// Situation of the offer held by the offer controller, offer being the controller's offer
+    pub(crate) fn new(offer :&'a FruitOffer, offer_controller :&'a FruitOfferController, player :&'a Player) -> Situation<'a> {
+        return Situation {
+            offer,
+            range: FruitStore::range_for_fruit(&offer.get_fruit(), &offer.get_season(), &offer.get_event()),
+            auction: offer_controller.get_auction(),
+            units_bought: offer_controller.get_units_bought(),
+            player,
+            offers_left: Config::AMOUNT_OF_OFFERS.saturating_sub(offer.get_id() + 1)
+        };
+    }
+
+    /// Middle of the offered fruit's base price range
+    pub(crate) fn get_midpoint(&self) -> Money {
+        let cents = (self.range.start().get_cents() + self.range.end().get_cents()) / 2;
+        return Money::from_cents(cents);
+    }
+
+    /// Price of buying the offer now, the next bid for auctions and the whole lot for bulk lots
+    pub(crate) fn get_lot_price(&self) -> Money {
+        return match self.auction {
+            Some(auction) => { auction.get_next_bid() }
+            None => { self.offer.get_price_after(self.units_bought) }
+        };
+    }
+
+    /// Price of one fruit when buying the offer now
+    pub(crate) fn get_unit_price(&self) -> Money {
+        return self.get_lot_price().per_unit(self.offer.get_units());
+    }
+
+    /// Price the store would pay for the offered grade when the fruit's base price is base_price
+    pub(crate) fn get_resale_price(&self, base_price :Money) -> Money {
+        return self.offer.get_quality().sell_price(base_price);
+    }
+
+    /// Best grade of the offered fruit the player holds, the grade selling hands over. None if the
+    /// player holds none of the fruit
+    pub(crate) fn get_best_held_quality(&self) -> Option<Quality> {
+        let fruit = self.offer.get_fruit();
+        return Quality::iter().find(|quality| self.player.get_amount_of_fruit_quality(fruit, *quality) > 0);
+    }
+
+    /// Price the store pays for the offered fruit right now, selling the best graded fruit held.
+    /// None if the player holds none of the fruit or the offer is an auction
+    pub(crate) fn get_sell_price(&self) -> Option<Money> {
+        if self.auction.is_some() {
+            return None;
+        }
+        return self.player.get_sell_price(self.offer);
+    }
+
+    /// Returns true if the player has the cash and storage to buy the offer now. Auctions can only
+    /// be bid on while the player does not hold the top bid
+    pub(crate) fn can_buy(&self) -> bool {
+        return !self.is_top_bidder() &&
+            self.get_lot_price() <= self.player.get_cash() &&
+            self.player.get_amount_of_fruits() + self.offer.get_units() <= Config::STORAGE_CAPACITY;
+    }
+
+    /// Returns true if the offer is an auction and the player holds its top bid
+    pub(crate) fn is_top_bidder(&self) -> bool {
+        return self.auction.is_some_and(|auction| auction.is_player_top_bidder());
+    }
+}
+
+/// Plays the game in place of the player. A strategy is asked what to do with the current offer
+/// every Config::BOT_MOVE_MILLIS and answers with the interaction it would press, the interaction
+/// is performed the same way as the player's keys. Strategies only buy, sell and move on to the next
+/// offer
+pub(crate) trait Strategy {
+    /// Name the strategy is picked by with --bot
+    fn get_name(&self) -> &'static str;
+
+    /// Interaction to perform on the offer, None to wait, such as while holding the top bid of an
+    /// auction
+    fn decide(&mut self, situation :&Situation) -> Option<PlayerInteractions>;
+}
+
+/// Names of all built-in strategies, each one can be created with by_name
+pub(crate) const NAMES: [&str; 3] = [Midpoint::NAME, Arbitrage::NAME, Random::NAME];
+
+/// Creates the built-in strategy with the name, strategies drawing random numbers are seeded with
+/// seed so a game can be replayed. Returns InvalidArgument if there is no strategy with the name
+pub(crate) fn by_name(name :&str, seed :u64) -> Result<Box<dyn Strategy>, GameError> {
+    return match name {
+        Midpoint::NAME => { Ok(Box::new(Midpoint::new())) }
+        Arbitrage::NAME => { Ok(Box::new(Arbitrage::new())) }
+        Random::NAME => { Ok(Box::new(Random::new(seed))) }
+        _ => { Err(GameError::InvalidArgument(format!("--bot {}", name))) }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fruit::{Fruit, OfferKind};
+
+    /// Grade B apples for sale at the price, with a base price range of 1.00 to 3.00 and the resale
+    /// price of grade B at the middle of it being 1.80
+    fn apples(price :Money, base_price :Money) -> FruitOffer {
+        return FruitOffer::scripted(0, Fruit::Apples, Quality::B, price, base_price, OfferKind::Fixed);
+    }
+
+    fn situation<'a>(offer :&'a FruitOffer, player :&'a Player, offers_left :u32) -> Situation<'a> {
+        return Situation {
+            offer,
+            range: Money::from_dollars(1)..=Money::from_dollars(3),
+            auction: None,
+            units_bought: 0,
+            player,
+            offers_left
+        };
+    }
+
+    /// Player holding one grade B apple bought for the price
+    fn holding_apple(price :Money) -> Player {
+        let mut player = Player::new(Config::STARTING_CASH);
+        player.buy_lot(Fruit::Apples, Quality::B, 1, price, 0, false).unwrap();
+        return player;
+    }
+
+    fn decide(strategy :&mut dyn Strategy, offer :&FruitOffer, player :&Player, offers_left :u32) -> Option<PlayerInteractions> {
+        return strategy.decide(&situation(offer, player, offers_left));
+    }
+
+    #[test]
+    fn midpoint_buys_below_the_middle_of_the_range() {
+        let player = Player::new(Config::STARTING_CASH);
+        let mut midpoint = Midpoint::new();
+        let cheap = apples(Money::from_cents(179), Money::from_dollars(2));
+        assert_eq!(decide(&mut midpoint, &cheap, &player, 50), Some(PlayerInteractions::Buy));
+        let fair = apples(Money::from_cents(180), Money::from_dollars(2));
+        assert_eq!(decide(&mut midpoint, &fair, &player, 50), Some(PlayerInteractions::NextOffer));
+        let broke = Player::new(Money::from_cents(100));
+        assert_eq!(decide(&mut midpoint, &cheap, &broke, 50), Some(PlayerInteractions::NextOffer));
+    }
+
+    #[test]
+    fn midpoint_sells_above_the_middle_of_the_range() {
+        let player = holding_apple(Money::from_dollars(1));
+        let mut midpoint = Midpoint::new();
+        let high = apples(Money::from_dollars(3), Money::from_cents(201));
+        assert_eq!(decide(&mut midpoint, &high, &player, 50), Some(PlayerInteractions::Sell));
+        let middle = apples(Money::from_dollars(3), Money::from_dollars(2));
+        assert_eq!(decide(&mut midpoint, &middle, &player, 50), Some(PlayerInteractions::NextOffer));
+    }
+
+    #[test]
+    fn arbitrage_buys_in_the_lowest_quarter_of_the_range() {
+        let player = Player::new(Config::STARTING_CASH);
+        let mut arbitrage = Arbitrage::new();
+        //Grade B sells for 1.35 at the top of the lowest quarter, 1.50
+        let cheap = apples(Money::from_cents(135), Money::from_dollars(2));
+        assert_eq!(decide(&mut arbitrage, &cheap, &player, 50), Some(PlayerInteractions::Buy));
+        let dear = apples(Money::from_cents(136), Money::from_dollars(2));
+        assert_eq!(decide(&mut arbitrage, &dear, &player, 50), Some(PlayerInteractions::NextOffer));
+        assert_eq!(decide(&mut arbitrage, &cheap, &player, Config::BOT_SELL_OFF_OFFERS - 1), Some(PlayerInteractions::NextOffer));
+    }
+
+    #[test]
+    fn arbitrage_sells_above_the_price_paid_or_when_selling_off() {
+        let player = holding_apple(Money::from_dollars(1));
+        let mut arbitrage = Arbitrage::new();
+        //Grade B sells for 90% of the base price rounded down, 1.01 at 1.13 and 1.00 at 1.12
+        let profitable = apples(Money::from_dollars(3), Money::from_cents(113));
+        assert_eq!(decide(&mut arbitrage, &profitable, &player, 50), Some(PlayerInteractions::Sell));
+        let at_a_loss = apples(Money::from_dollars(3), Money::from_cents(112));
+        assert_eq!(decide(&mut arbitrage, &at_a_loss, &player, 50), Some(PlayerInteractions::NextOffer));
+        assert_eq!(decide(&mut arbitrage, &at_a_loss, &player, Config::BOT_SELL_OFF_OFFERS - 1), Some(PlayerInteractions::Sell));
+    }
+
+    #[test]
+    fn random_plays_the_same_with_the_same_seed() {
+        let player = Player::new(Config::STARTING_CASH);
+        let offer = apples(Money::from_dollars(1), Money::from_dollars(2));
+        let (mut first, mut second) = (Random::new(7), Random::new(7));
+        let decisions :Vec<_> = (0..100).map(|_| decide(&mut first, &offer, &player, 50)).collect();
+        for decision in &decisions {
+            assert_eq!(*decision, decide(&mut second, &offer, &player, 50));
+        }
+        for expected in &[Some(PlayerInteractions::Buy), Some(PlayerInteractions::Sell), Some(PlayerInteractions::NextOffer), None] {
+            assert!(decisions.contains(expected));
+        }
+    }
+
+    #[test]
+    fn strategies_are_created_by_name() {
+        for name in NAMES.iter() {
+            assert_eq!(by_name(name, 0).unwrap().get_name(), *name);
+        }
+        let error = by_name("genius", 0).err();
+        assert!(matches!(error, Some(GameError::InvalidArgument(arg)) if arg == "--bot genius"));
+    }
+}
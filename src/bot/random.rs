@@ -0,0 +1,34 @@
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+use crate::bot::{Situation, Strategy};
+use crate::console::input::PlayerInteractions;
+
+/// Buys, sells, skips or waits at random, as a baseline the other strategies should beat.
+/// Seeded so a game with the same fruit store seed is played the same way
+pub(crate) struct Random {
+    rng: StdRng
+}
+
+impl Random {
+    pub(crate) const NAME: &'static str = "random";
+
+    pub(crate) fn new(seed :u64) -> Random {
+        return Random { rng: StdRng::seed_from_u64(seed) };
+    }
+}
+
+impl Strategy for Random {
+    fn get_name(&self) -> &'static str {
+        return Random::NAME;
+    }
+
+    fn decide(&mut self, _situation :&Situation) -> Option<PlayerInteractions> {
+        return match self.rng.gen_range(0..4) {
+            0 => { Some(PlayerInteractions::Buy) }
+            1 => { Some(PlayerInteractions::Sell) }
+            2 => { Some(PlayerInteractions::NextOffer) }
+            _ => { None }
+        };
+    }
+}
@@ -0,0 +1,165 @@
+use crate::game::error::GameError;
+
+/// Options given on the command line
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct Args {
+    /// Play the guided tutorial instead of a game
+    pub(crate) tutorial: bool,
+    /// Announce the game for screen readers instead of drawing it, see Accessibility
+    pub(crate) screen_reader: bool,
+    /// Language of the message catalog to play in, see Locale::load
+    pub(crate) lang: Option<String>,
+    /// Play a ranked game in which the advisor can not be shown, see advisor::Advisor
+    pub(crate) ranked: bool,
+    /// Search for the optimal score to compare against at game over, see solver
+    pub(crate) optimal: bool,
+    /// Name of the built-in strategy playing the game instead of the player, see bot::by_name
+    pub(crate) bot: Option<String>,
+    /// Play games headless with a bot and report their scores instead of playing, see simulation
+    pub(crate) simulate: bool,
+    /// Play every bot headless on the same games and rank them instead of playing, see tournament
+    pub(crate) tournament: bool,
+    /// Print the optimal plan for the game of a seed instead of playing, see solver
+    pub(crate) solve: bool,
+    /// Amount of games to simulate, for each bot in a tournament
+    pub(crate) games: Option<u32>,
+    /// Seed of the game to play or solve, or of the first simulated game
+    pub(crate) seed: Option<u64>,
+    /// File to write the tournament's leaderboard to as CSV
+    pub(crate) csv: Option<String>,
+    /// File to write the tournament's leaderboard to as JSON
+    pub(crate) json: Option<String>,
+    /// Print usage and exit
+    pub(crate) help: bool
+}
+
+impl Args {
+    /// Usage printed for --help and for arguments which can not be read
+    pub(crate) const USAGE: &'static str = "usage: fruitstore [--tutorial] [--screen-reader] [--lang <language>] [--ranked] [--optimal] [--bot midpoint|arbitrage|random] [--seed <seed>] [--help]
+       fruitstore simulate [--bot <name>] [--games <count>] [--seed <seed>]
+       fruitstore tournament [--games <count>] [--seed <seed>] [--csv <file>] [--json <file>]
+       fruitstore solve [--seed <seed>]";
+
+    /// Read options from the arguments, without the program name. Returns an error naming the
+    /// first argument which is no option, an option missing its value or given a value which is no
+    /// number, or an option given without the command it belongs to
+    pub(crate) fn parse(mut args :impl Iterator<Item = String>) -> Result<Args, GameError> {
+        let mut parsed = Args::default();
+        while let Some(arg) = args.next() {
+            match arg.as_str(){
+                "--lang" => {
+                    parsed.lang = Some(args.next().ok_or(GameError::InvalidArgument(arg))?);
+                }
+                _ if arg.starts_with("--lang=") => {
+                    parsed.lang = Some(String::from(&arg["--lang=".len()..]));
+                }
+                "--bot" => {
+                    parsed.bot = Some(args.next().ok_or(GameError::InvalidArgument(arg))?);
+                }
+                _ if arg.starts_with("--bot=") => {
+                    parsed.bot = Some(String::from(&arg["--bot=".len()..]));
+                }
+                "--games" => {
+                    parsed.games = Some(Args::count(args.next(), arg)?);
+                }
+                "--seed" => {
+                    parsed.seed = Some(Args::number(args.next(), arg)?);
+                }
+                "--csv" => {
+                    parsed.csv = Some(args.next().ok_or(GameError::InvalidArgument(arg))?);
+                }
+                "--json" => {
+                    parsed.json = Some(args.next().ok_or(GameError::InvalidArgument(arg))?);
+                }
+                "simulate" => { parsed.simulate = true; }
+                "tournament" => { parsed.tournament = true; }
+                "solve" => { parsed.solve = true; }
+                "--tutorial" => { parsed.tutorial = true; }
+                "--screen-reader" => { parsed.screen_reader = true; }
+                "--ranked" => { parsed.ranked = true; }
+                "--optimal" => { parsed.optimal = true; }
+                "-h" | "--help" => { parsed.help = true; }
+                _ => { return Err(GameError::InvalidArgument(arg)); }
+            }
+        }
+        let batch = parsed.simulate || parsed.tournament;
+        let headless = batch || parsed.solve;
+        let misplaced = [
+            ("tournament", parsed.simulate && parsed.tournament),
+            ("solve", parsed.solve && batch),
+            ("--optimal", headless && parsed.optimal),
+            ("--tutorial", headless && parsed.tutorial),
+            ("--screen-reader", headless && parsed.screen_reader),
+            ("--lang", headless && parsed.lang.is_some()),
+            ("--ranked", headless && parsed.ranked),
+            ("--games", !batch && parsed.games.is_some()),
+            ("--bot", parsed.solve && parsed.bot.is_some()),
+            ("--bot", parsed.tournament && parsed.bot.is_some()),
+            ("--csv", !parsed.tournament && parsed.csv.is_some()),
+            ("--json", !parsed.tournament && parsed.json.is_some())
+        ];
+        if let Some((option, _)) = misplaced.iter().find(|(_, misplaced)| *misplaced) {
+            return Err(GameError::InvalidArgument(String::from(*option)));
+        }
+        return Ok(parsed);
+    }
+
+    /// Value of the option as a number, an error naming the option if missing or no number
+    fn number(value :Option<String>, option :String) -> Result<u64, GameError> {
+        return value.and_then(|value| value.parse().ok()).ok_or(GameError::InvalidArgument(option));
+    }
+
+    /// Value of the option as a count of at least 1, an error naming the option if missing or no count
+    fn count(value :Option<String>, option :String) -> Result<u32, GameError> {
+        return value.and_then(|value| value.parse().ok()).filter(|count| *count > 0).ok_or(GameError::InvalidArgument(option));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args :&[&str]) -> Result<Args, GameError> {
+        return Args::parse(args.iter().map(|arg| String::from(*arg)));
+    }
+
+    /// Name of the argument parsing rejected, None if it was accepted
+    fn rejected(args :&[&str]) -> Option<String> {
+        return match parse(args) {
+            Err(GameError::InvalidArgument(arg)) => { Some(arg) }
+            _ => { None }
+        };
+    }
+
+    #[test]
+    fn game_options_are_read() {
+        let args = parse(&["--tutorial", "--screen-reader", "--lang=de", "--ranked", "--optimal", "--seed", "7"]).unwrap();
+        assert!(args.tutorial && args.screen_reader && args.ranked && args.optimal);
+        assert_eq!(args.lang, Some(String::from("de")));
+        assert_eq!(args.seed, Some(7));
+        assert_eq!(parse(&["--lang", "de"]).unwrap().lang, Some(String::from("de")));
+    }
+
+    #[test]
+    fn game_options_are_rejected_for_commands_without_a_game_screen() {
+        for &command in &["simulate", "tournament", "solve"] {
+            assert_eq!(rejected(&[command, "--tutorial"]), Some(String::from("--tutorial")));
+            assert_eq!(rejected(&[command, "--screen-reader"]), Some(String::from("--screen-reader")));
+            assert_eq!(rejected(&[command, "--lang", "de"]), Some(String::from("--lang")));
+            assert_eq!(rejected(&["--lang=de", command]), Some(String::from("--lang")));
+            assert_eq!(rejected(&[command, "--ranked"]), Some(String::from("--ranked")));
+            assert_eq!(rejected(&[command, "--optimal"]), Some(String::from("--optimal")));
+            assert_eq!(rejected(&[command, "--seed", "7"]), None);
+        }
+    }
+
+    #[test]
+    fn options_of_other_commands_are_rejected() {
+        assert_eq!(rejected(&["--games", "3"]), Some(String::from("--games")));
+        assert_eq!(rejected(&["simulate", "--csv", "a.csv"]), Some(String::from("--csv")));
+        assert_eq!(rejected(&["solve", "--bot", "random"]), Some(String::from("--bot")));
+        assert_eq!(rejected(&["simulate", "tournament"]), Some(String::from("tournament")));
+        assert_eq!(rejected(&["--games", "0", "simulate"]), Some(String::from("--games")));
+        assert_eq!(rejected(&["--frobnicate"]), Some(String::from("--frobnicate")));
+    }
+}
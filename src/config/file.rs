@@ -0,0 +1,96 @@
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+
+use crate::config::Config;
+use crate::game::error::GameError;
+
+/// Setting read from the config file, line is where it was found so errors can point to it
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct ConfigEntry {
+    pub(crate) section: String,
+    pub(crate) name: String,
+    pub(crate) value: String,
+    pub(crate) line: usize
+}
+
+/// Player settings read from the config file. The file holds `name = value` lines grouped under
+/// `[section]` headers, empty lines and lines starting with '#' are skipped.
+/// # Examples
+/// '''
+/// [keys]
+/// buy = b, Up
+/// '''
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ConfigFile {
+    path: String,
+    entries: Vec<ConfigEntry>
+}
+
+impl ConfigFile {
+    /// Read the config file named by the FRUITSTORE_CONFIG environment variable, or else
+    /// Config::CONFIG_FILE from the working directory. A missing default config file leaves every
+    /// setting at its default, a missing config file which was named explicitly is an error
+    pub(crate) fn load() -> Result<ConfigFile, GameError> {
+        let (path, explicit) = match env::var(Config::CONFIG_FILE_ENV) {
+            Ok(path) => { (path, true) }
+            Err(_) => { (String::from(Config::CONFIG_FILE), false) }
+        };
+        match fs::read_to_string(&path) {
+            Ok(text) => {
+                return ConfigFile::parse(&path, &text);
+            }
+            Err(error) if error.kind() == ErrorKind::NotFound && !explicit => {
+                return Ok(ConfigFile::default());
+            }
+            Err(error) => {
+                return Err(GameError::InvalidConfig(format!("{}: {}", path, error)));
+            }
+        }
+    }
+
+    /// Parse the text of the config file at path, returns an error naming the line which could not
+    /// be parsed
+    pub(crate) fn parse(path :&str, text :&str) -> Result<ConfigFile, GameError> {
+        let mut config = ConfigFile { path: String::from(path), entries: Vec::new() };
+        let mut section = String::new();
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') && line.ends_with(']') {
+                section = line[1..line.len() - 1].trim().to_lowercase();
+                continue;
+            }
+            match line.split_once('=') {
+                Some((name, value)) if !name.trim().is_empty() => {
+                    config.entries.push(ConfigEntry {
+                        section: section.clone(),
+                        name: name.trim().to_lowercase(),
+                        value: String::from(value.trim()),
+                        line: line_number
+                    });
+                }
+                _ => {
+                    return Err(config.error(Some(line_number), "expected '[section]' or 'name = value'"));
+                }
+            }
+        }
+        return Ok(config);
+    }
+
+    /// Returns error about a setting in the config file, naming the file and the line if given
+    pub(crate) fn error(&self, line :Option<usize>, message :&str) -> GameError {
+        return match line {
+            Some(line) => { GameError::InvalidConfig(format!("{}: line {}: {}", self.path, line, message)) }
+            None => { GameError::InvalidConfig(format!("{}: {}", self.path, message)) }
+        };
+    }
+
+    /// Settings of the section in the order they appear in the file
+    pub(crate) fn section<'a>(&'a self, section :&'a str) -> impl Iterator<Item = &'a ConfigEntry> + 'a {
+        return self.entries.iter().filter(move |entry| entry.section == section);
+    }
+}
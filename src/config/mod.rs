@@ -1,29 +1,226 @@
+pub mod args;
+pub mod file;
+
 use crate::fruit::Fruit;
-use std::ops::Range;
+use crate::fruit::market_event::MarketEvent;
+use crate::game::tutorial::TutorialStep;
+use crate::console::input::PlayerInteractions;
+use crate::fruit::season::Season;
+use crate::fruit::quality::Quality;
+use crate::game::money::Money;
+use std::ops::RangeInclusive;
 
 pub(crate) struct Config{}
 
 /// Static configuration settings
 impl Config {
+    /// Config file read from the working directory when FRUITSTORE_CONFIG does not name one
+    pub(crate) const CONFIG_FILE: &'static str = "fruitstore.conf";
+    /// Environment variable naming the config file to read
+    pub(crate) const CONFIG_FILE_ENV: &'static str = "FRUITSTORE_CONFIG";
+    /// Directory the message catalogs are read from when FRUITSTORE_LANG_DIR does not name one
+    pub(crate) const LANG_DIR: &'static str = "lang";
+    /// Environment variable naming the directory to read message catalogs from
+    pub(crate) const LANG_DIR_ENV: &'static str = "FRUITSTORE_LANG_DIR";
     /// How much cash a player should start with
-    pub(crate) const STARTING_CASH: u32 = 50;
+    pub(crate) const STARTING_CASH: Money = Money::from_dollars(50);
+    /// Lowest price the store asks or pays for anything, prices scaled below it are raised to it
+    pub(crate) const MIN_PRICE: Money = Money::from_cents(10);
+    /// Amount of fruit the player's storage can hold
+    pub(crate) const STORAGE_CAPACITY: u32 = 60;
     /// Amount of offers which should occur before game ends
     pub(crate) const AMOUNT_OF_OFFERS: u32 = 100;
+    /// Interval at which the game loop redraws the offer timeout while waiting for player input
+    pub(crate) const RENDER_TICK_MILLIS: u32 = 50;
+    /// Interval at which the input thread checks if it should stop while waiting for a key
+    pub(crate) const INPUT_POLL_MILLIS: u32 = 100;
+    /// Time the rest of an escape sequence, such as an arrow key, may take to arrive after its escape
+    /// byte before the escape byte is taken to be the Esc key
+    pub(crate) const ESCAPE_SEQUENCE_MILLIS: u32 = 25;
     /// Duration before key up should get registered
     pub(crate) const KEY_RELEASE_MILLIS: u32 = 500;
+    /// Interval at which a bot playing with --bot makes its moves, longer than KEY_RELEASE_MILLIS
+    /// so repeated buys are not taken for a key held over from the previous offer
+    pub(crate) const BOT_MOVE_MILLIS: u32 = 600;
+    /// Amount of offers before game over during which the arbitrage bot sells off its fruit
+    pub(crate) const BOT_SELL_OFF_OFFERS: u32 = 10;
+    /// Expected value per fruit, in percent of the fruit's price, from which the advisor rates an
+    /// offer a great buy
+    pub(crate) const ADVISOR_GREAT_BUY_PERCENT: u32 = 25;
+    /// Amount of games played by simulate when not given with --games
+    pub(crate) const SIMULATION_GAMES: u32 = 1000;
+    /// Terminal width from which the inventory and price history panels are shown side by side
+    pub(crate) const SPLIT_PANELS_MIN_WIDTH: u16 = 80;
+    /// Amount of most recent prices shown in each fruit's price chart
+    pub(crate) const SPARKLINE_LENGTH: usize = 16;
+    /// Amount of messages kept in the event log panel
+    pub(crate) const EVENT_LOG_LENGTH: usize = 50;
     /// Minimum duration offer should last
     pub(crate) const OFFER_DURATION_MIN_MILLIS: u32 = 2300;
     /// Maximum duration offer should last
     pub(crate) const OFFER_DURATION_MAX_MILLIS: u32 = 5400;
+    /// Duration of the tutorial's offers, long enough to read the tutorial's prompts
+    pub(crate) const TUTORIAL_OFFER_DURATION_MILLIS: u32 = 30000;
+    /// Seed for the fruit store's random generator, the same seed gives the same offers and
+    /// market events. None picks a new random seed for every game
+    pub(crate) const SEED: Option<u64> = None;
+    /// Chance in percent that a market event fires on an offer when no event is active
+    pub(crate) const MARKET_EVENT_CHANCE_PERCENT: u32 = 8;
+    /// Chance in percent that an offer is an auction of a lot instead of a fixed price offer
+    pub(crate) const AUCTION_CHANCE_PERCENT: u32 = 10;
+    /// Minimum amount of fruit in an auctioned lot
+    pub(crate) const AUCTION_LOT_MIN: u32 = 3;
+    /// Maximum amount of fruit in an auctioned lot
+    pub(crate) const AUCTION_LOT_MAX: u32 = 8;
+    /// Opening bid as percentage of what the lot would cost at the offer's price
+    pub(crate) const AUCTION_OPENING_PERCENT: u32 = 50;
+    /// Amount each bid raises the top bid by as percentage of what the lot would cost at the offer's price
+    pub(crate) const AUCTION_INCREMENT_PERCENT: u32 = 5;
+    /// Lowest limit of the rival bidder as percentage of what the lot would cost at the offer's price
+    pub(crate) const AUCTION_RIVAL_LIMIT_MIN_PERCENT: u32 = 70;
+    /// Highest limit of the rival bidder as percentage of what the lot would cost at the offer's price
+    pub(crate) const AUCTION_RIVAL_LIMIT_MAX_PERCENT: u32 = 130;
+    /// How far into the offer's duration, in percent, the rival places its opening bid
+    pub(crate) const AUCTION_RIVAL_OPEN_PERCENT: u32 = 30;
+    /// Time it takes the rival to answer a bid from the player
+    pub(crate) const AUCTION_RIVAL_REACTION_MILLIS: u32 = 700;
+    /// Chance in percent that an offer is a bulk lot sold as a whole
+    pub(crate) const BULK_CHANCE_PERCENT: u32 = 8;
+    /// Minimum amount of fruit in a bulk lot
+    pub(crate) const BULK_LOT_MIN: u32 = 6;
+    /// Maximum amount of fruit in a bulk lot
+    pub(crate) const BULK_LOT_MAX: u32 = 12;
+    /// Price of a bulk lot as percentage of what the fruit would cost one by one
+    pub(crate) const BULK_PRICE_PERCENT: u32 = 80;
+    /// Chance in percent that an offer comes with a volume discount
+    pub(crate) const TIERED_CHANCE_PERCENT: u32 = 8;
+    /// Amount of fruit which has to be bought from an offer before the volume discount applies
+    pub(crate) const TIERED_THRESHOLD: u32 = 10;
+    /// Discounted price as percentage of the offer's price
+    pub(crate) const TIERED_PRICE_PERCENT: u32 = 70;
+    /// Amount of offers each season lasts, seasons cycle spring, summer, autumn, winter
+    pub(crate) const SEASON_LENGTH_OFFERS: u32 = 25;
 
-    /// Returns price range for the fruit in question
-    pub(crate) fn range_for_fruit(fruit :&Fruit)->Range<u32>{
+    /// Returns the season in which the fruit in question is harvested
+    pub(crate) fn season_for_fruit(fruit :&Fruit)->Season{
         match fruit{
-            Fruit::Apples => {3..10}
-            Fruit::Banana => {1..4}
-            Fruit::Coconut => {3..7}
-            Fruit::DragonFruit => {10..31}
-            Fruit::Elderberry => {1..11}
+            Fruit::Apples => {Season::Autumn}
+            Fruit::Banana => {Season::Winter}
+            Fruit::Coconut => {Season::Summer}
+            Fruit::DragonFruit => {Season::Summer}
+            Fruit::Elderberry => {Season::Spring}
+        }
+    }
+
+    /// Returns price range for the fruit in question during the season in question, both ends
+    /// included. Prices are picked from the range to the cent
+    pub(crate) fn range_for_fruit(fruit :&Fruit, season :&Season)->RangeInclusive<Money>{
+        let in_season = Config::season_for_fruit(fruit) == *season;
+        let (start, end) = match (fruit, in_season){
+            (Fruit::Apples, true) => {(2, 6)}
+            (Fruit::Apples, false) => {(4, 10)}
+            (Fruit::Banana, true) => {(1, 2)}
+            (Fruit::Banana, false) => {(2, 4)}
+            (Fruit::Coconut, true) => {(2, 4)}
+            (Fruit::Coconut, false) => {(4, 8)}
+            (Fruit::DragonFruit, true) => {(8, 20)}
+            (Fruit::DragonFruit, false) => {(15, 35)}
+            (Fruit::Elderberry, true) => {(1, 5)}
+            (Fruit::Elderberry, false) => {(4, 12)}
+        };
+        return Money::from_dollars(start)..=Money::from_dollars(end);
+    }
+
+    /// Returns how often the fruit in question is offered compared to the other fruits during the
+    /// season in question
+    pub(crate) fn weight_for_fruit(fruit :&Fruit, season :&Season)->u32{
+        let in_season = Config::season_for_fruit(fruit) == *season;
+        match (fruit, in_season){
+            (Fruit::Apples, true) => {150}
+            (Fruit::Apples, false) => {90}
+            (Fruit::Banana, true) => {180}
+            (Fruit::Banana, false) => {80}
+            (Fruit::Coconut, true) => {140}
+            (Fruit::Coconut, false) => {60}
+            (Fruit::DragonFruit, true) => {120}
+            (Fruit::DragonFruit, false) => {30}
+            (Fruit::Elderberry, true) => {200}
+            (Fruit::Elderberry, false) => {40}
+        }
+    }
+
+    /// Returns how often offers of the grade in question occur compared to the other grades
+    pub(crate) fn weight_for_quality(quality :&Quality)->u32{
+        match quality{
+            Quality::A => {20}
+            Quality::B => {50}
+            Quality::C => {30}
+        }
+    }
+
+    /// Returns the percentage of the fruit's base price which the store asks for the grade
+    pub(crate) fn price_percent_for_quality(quality :&Quality)->u32{
+        match quality{
+            Quality::A => {160}
+            Quality::B => {100}
+            Quality::C => {50}
         }
     }
-}
\ No newline at end of file
+
+    /// Returns the percentage of the fruit's base price which the store pays when buying back the grade,
+    /// below the grade's price percentage so fruit sold back on the offer it was bought on loses money
+    pub(crate) fn resale_percent_for_quality(quality :&Quality)->u32{
+        match quality{
+            Quality::A => {140}
+            Quality::B => {90}
+            Quality::C => {45}
+        }
+    }
+
+    /// Returns the amount of offers fruit of the grade keeps before it spoils in the player's inventory
+    pub(crate) fn shelf_life_for_quality(quality :&Quality)->u32{
+        match quality{
+            Quality::A => {30}
+            Quality::B => {20}
+            Quality::C => {8}
+        }
+    }
+
+    /// Returns the file the transaction ledger of the game played with seed is exported to
+    pub(crate) fn ledger_export_path(seed :u64)->String{
+        return format!("fruitstore-ledger-{}.csv", seed);
+    }
+
+    /// Returns the steps of the tutorial, walking the player through buying, checking prices,
+    /// selling and skipping before ending the game
+    pub(crate) fn tutorial_steps()->Vec<TutorialStep>{
+        vec![
+            TutorialStep{ name: "welcome",
+                interaction: PlayerInteractions::Buy, fruit: Fruit::Banana, quality: Quality::B, base_price: Money::from_dollars(2) },
+            TutorialStep{ name: "info",
+                interaction: PlayerInteractions::Info, fruit: Fruit::Banana, quality: Quality::B, base_price: Money::from_dollars(3) },
+            TutorialStep{ name: "sell",
+                interaction: PlayerInteractions::Sell, fruit: Fruit::Banana, quality: Quality::B, base_price: Money::from_dollars(6) },
+            TutorialStep{ name: "skip",
+                interaction: PlayerInteractions::NextOffer, fruit: Fruit::Coconut, quality: Quality::A, base_price: Money::from_dollars(8) },
+            TutorialStep{ name: "end",
+                interaction: PlayerInteractions::Exit, fruit: Fruit::Apples, quality: Quality::B, base_price: Money::from_dollars(4) },
+        ]
+    }
+
+    /// Returns the market events which can occur during a game
+    pub(crate) fn market_events()->Vec<MarketEvent>{
+        vec![
+            MarketEvent{ name: "banana_blight", fruit: Fruit::Banana,
+                price_percent: 200, weight_percent: 50, duration_offers: 8 },
+            MarketEvent{ name: "coconut_glut", fruit: Fruit::Coconut,
+                price_percent: 50, weight_percent: 300, duration_offers: 6 },
+            MarketEvent{ name: "apple_harvest", fruit: Fruit::Apples,
+                price_percent: 70, weight_percent: 200, duration_offers: 10 },
+            MarketEvent{ name: "dragon_fruit_craze", fruit: Fruit::DragonFruit,
+                price_percent: 150, weight_percent: 150, duration_offers: 6 },
+            MarketEvent{ name: "elderberry_frost", fruit: Fruit::Elderberry,
+                price_percent: 180, weight_percent: 40, duration_offers: 8 },
+        ]
+    }
+}
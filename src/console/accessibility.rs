@@ -0,0 +1,89 @@
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::config::file::ConfigFile;
+use crate::game::error::GameError;
+
+/// How much the screen reader mode announces, each level announcing all the lower levels announce
+#[derive(Clone, Copy, Debug, EnumIter, Eq, Ord, PartialEq, PartialOrd)]
+pub(crate) enum Verbosity {
+    /// New offers, bids, the player's transactions, replies to keys and a quarter of the time left
+    Terse,
+    /// Also market news, the start of seasons, spoiled fruit and half of the time left
+    Normal,
+    /// Also the buy back prices of offers, the storage used and more steps of the time left
+    Verbose
+}
+
+impl Verbosity {
+    /// Name used for the verbosity in the config file
+    pub(crate) fn get_name(&self) -> &'static str {
+        match self{
+            Verbosity::Terse => {"terse"}
+            Verbosity::Normal => {"normal"}
+            Verbosity::Verbose => {"verbose"}
+        }
+    }
+
+    /// Least verbosity at which the percentage of time remaining is announced, None if it is not.
+    /// Percentages are announced once, when the time remaining drops to them
+    pub(crate) fn for_time_remaining(percent :u32) -> Option<Verbosity> {
+        return match percent {
+            25 => { Some(Verbosity::Terse) }
+            50 => { Some(Verbosity::Normal) }
+            75 | 10 => { Some(Verbosity::Verbose) }
+            _ => { None }
+        };
+    }
+}
+
+/// Accessibility settings of the config file's [accessibility] section. In screen reader mode the
+/// game screen is not drawn, instead changes of the game's state are written as plain lines of
+/// text, without colors or cursor movement, for the screen reader to read out.
+/// # Examples
+/// '''
+/// [accessibility]
+/// screen_reader = on
+/// verbosity = terse
+/// '''
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Accessibility {
+    pub(crate) screen_reader: bool,
+    pub(crate) verbosity: Verbosity
+}
+
+impl Accessibility {
+    /// Settings of the config file's [accessibility] section, screen reader mode is off and
+    /// verbosity normal unless set. Returns an error if a setting can not be read
+    pub(crate) fn from_config(config :&ConfigFile) -> Result<Accessibility, GameError> {
+        let mut accessibility = Accessibility { screen_reader: false, verbosity: Verbosity::Normal };
+        for entry in config.section("accessibility") {
+            match entry.name.as_str(){
+                "screen_reader" => {
+                    accessibility.screen_reader = match entry.value.to_lowercase().as_str(){
+                        "on" | "true" | "yes" => { true }
+                        "off" | "false" | "no" => { false }
+                        _ => { return Err(config.error(Some(entry.line), &format!("expected on or off for screen_reader, not '{}'", entry.value))); }
+                    };
+                }
+                "verbosity" => {
+                    accessibility.verbosity = Verbosity::iter()
+                        .find(|verbosity| verbosity.get_name() == entry.value.to_lowercase())
+                        .ok_or_else(|| config.error(Some(entry.line), &format!("unknown verbosity '{}', expected terse, normal or verbose", entry.value)))?;
+                }
+                _ => {
+                    return Err(config.error(Some(entry.line), &format!("unknown accessibility setting '{}'", entry.name)));
+                }
+            }
+        }
+        return Ok(accessibility);
+    }
+
+    /// Verbosity of the screen reader's announcements, None when the game screen is drawn instead
+    pub(crate) fn announcements(&self) -> Option<Verbosity> {
+        if self.screen_reader {
+            return Some(self.verbosity);
+        }
+        return None;
+    }
+}
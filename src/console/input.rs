@@ -1,93 +1,141 @@
-use termion::event::Key;
+use strum_macros::EnumIter;
+use termion::event::{Event, MouseButton, MouseEvent};
 use termion::input::TermRead;
 
-use std::io::{stdin};
+use std::io::{Error, ErrorKind, Read};
 use std::thread;
 use std::thread::JoinHandle;
-use std::time::Duration;
 use std::sync::{Arc, mpsc};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::mpsc::Receiver;
+use std::time::Instant;
 
-/// All interactions player can perform
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+use crate::game::GameEvent;
+use crate::game::action::ShownOffer;
+use crate::config::Config;
+
+/// All interactions player can perform, see key_map::KeyMap for the keys they are bound to
+#[derive(Clone, Debug, EnumIter, Eq, Hash, PartialEq)]
 pub(crate) enum PlayerInteractions{
-    Buy, //< Key: b, buy current offer
-    Sell, //< Key: s, sell current offer
-    NextOffer, //< Key: n, instantly go to next offer
-    Exit, //< Key: e, end game and exit to prompt
-    Info //< Key: i, print current fruit's price range
+    Buy, //< Default key: b, buy current offer
+    Sell, //< Default key: s, sell current offer
+    NextOffer, //< Default key: n, instantly go to next offer
+    Info, //< Default key: i, print current fruit's price range
+    Charts, //< Default key: h, show or hide the price charts
+    Advisor, //< Default key: a, show or hide the advisor rating the current offer
+    Export, //< Default key: x, export the transaction ledger as CSV
+    Help, //< Default key: ?, show or hide the help, the game clock is paused while it is shown
+    Pause, //< Default key: p, pause or resume the game clock
+    Exit //< Default keys: e, q, c and Ctrl-c, end game and exit to prompt
+}
+
+impl PlayerInteractions {
+    /// Name of the interaction in the config file's [keys] section
+    pub(crate) fn get_name(&self) -> &'static str {
+        match self{
+            PlayerInteractions::Buy => {"buy"}
+            PlayerInteractions::Sell => {"sell"}
+            PlayerInteractions::NextOffer => {"next_offer"}
+            PlayerInteractions::Info => {"info"}
+            PlayerInteractions::Charts => {"charts"}
+            PlayerInteractions::Advisor => {"advisor"}
+            PlayerInteractions::Export => {"export"}
+            PlayerInteractions::Help => {"help"}
+            PlayerInteractions::Pause => {"pause"}
+            PlayerInteractions::Exit => {"exit"}
+        }
+    }
 }
 
-/// Player handler containing thread which takes console input from player.
+/// Player handler containing thread which takes console input from player and forwards each key and
+/// click as a GameEvent to the game loop. Keys and clicks are stamped with the offer shown and the
+/// time when they were read so the game loop can tell which offer the player meant.
 pub(crate) struct PlayerInteractionThreadHandler {
     keyboard_thread_run :Arc<AtomicBool>,
-    rx: Receiver<char>
+    thread: Option<JoinHandle<()>>
 }
 
 impl PlayerInteractionThreadHandler {
     ///Start a new console thread to capture player input. Stop must be called for the thread to end.
-    pub(crate) fn new()-> PlayerInteractionThreadHandler {
-        let (tx,rx) = mpsc::channel();
+    pub(crate) fn new(send_event :mpsc::Sender<GameEvent>, shown_offer :ShownOffer)-> PlayerInteractionThreadHandler {
         let keyboard_thread_run: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
         keyboard_thread_run.swap(true,Ordering::Relaxed);
         let keyboard_thread_run_2 = Arc::clone(&keyboard_thread_run);
-        read_thread(keyboard_thread_run_2, tx);
-        return PlayerInteractionThreadHandler { keyboard_thread_run, rx };
+        let thread = read_thread(keyboard_thread_run_2, send_event, shown_offer);
+        return PlayerInteractionThreadHandler { keyboard_thread_run, thread: Some(thread) };
     }
-    /// Stops console thread by signalling exit
+    /// Stops console thread by signalling exit and waits for it to end
     pub(crate) fn stop(&mut self){
         self.keyboard_thread_run.swap(false,Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
     }
-    /// Consume last characters inputted by the user and output associated player interaction
-    pub(crate) fn get_player_interaction(&self)->Option<PlayerInteractions>{
-        let iter = self.rx.try_iter().next();
-        match iter{
-            None => {}
-            Some(c) => {
-                match c{
-                    'b' => {
-                        return Some(PlayerInteractions::Buy);
-                    },
-                    's' => {
-                        return Some(PlayerInteractions::Sell);
-                    },
-                    'q' | 'e' | 'c' => {
-                        return Some(PlayerInteractions::Exit);
-                    }
-                    'n' =>{
-                        return Some(PlayerInteractions::NextOffer);
-                    }
-                    'i' =>{
-                        return Some(PlayerInteractions::Info);
-                    }
-                    _ =>{}
-                }
+}
+
+/// Unbuffered stdin, std's stdin buffers input which would hide keys already read from the
+/// terminal from poll
+struct RawStdin {}
+
+impl RawStdin {
+    fn read_raw(buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = unsafe { libc::read(libc::STDIN_FILENO, buf.as_mut_ptr() as *mut libc::c_void, buf.len()) };
+        if read < 0 {
+            return Err(Error::last_os_error());
+        }
+        return Ok(read as usize);
+    }
+}
+
+impl Read for RawStdin {
+    /// Termion tells the Esc key apart from escape sequences such as arrow keys by whether the
+    /// escape byte is read alone. The rest of a sequence may arrive a moment after its escape byte,
+    /// so a lone escape byte waits briefly for more bytes before it is taken to be the Esc key
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = RawStdin::read_raw(buf)?;
+        if read == 1 && buf[0] == b'\x1b' && buf.len() > 1 {
+            let mut poll_fd = libc::pollfd{ fd: libc::STDIN_FILENO, events: libc::POLLIN, revents: 0 };
+            if unsafe { libc::poll(&mut poll_fd, 1, Config::ESCAPE_SEQUENCE_MILLIS as i32) } > 0 {
+                return Ok(1 + RawStdin::read_raw(&mut buf[1..])?);
             }
         }
-        return None;
+        return Ok(read);
     }
 }
 
-/// Create console thread to read keys and output read chars. Checks keyboard_thread_run to determine
-/// when it is time to return.
-fn read_thread(keyboard_thread_run :Arc<AtomicBool>, send_key : mpsc::Sender<char>) ->JoinHandle<()>{
-    let stdin = stdin();
+/// Create console thread to read keys and left clicks and send them as game events, other mouse
+/// events are left out. Checks keyboard_thread_run to determine when it is time to return. Instead
+/// of blocking on stdin until the next key arrives, stdin is polled with a timeout so the thread
+/// notices when it is asked to stop.
+fn read_thread(keyboard_thread_run :Arc<AtomicBool>, send_event : mpsc::Sender<GameEvent>, shown_offer :ShownOffer) ->JoinHandle<()>{
     return thread::spawn(move || {
-        for c in stdin.keys() {
-            if !keyboard_thread_run.load(Ordering::Relaxed) {
+        let mut poll_fd = libc::pollfd{ fd: libc::STDIN_FILENO, events: libc::POLLIN, revents: 0 };
+        let mut events = RawStdin{}.events();
+        while keyboard_thread_run.load(Ordering::Relaxed) {
+            let ready = unsafe { libc::poll(&mut poll_fd, 1, Config::INPUT_POLL_MILLIS as i32) };
+            if ready < 0 && Error::last_os_error().kind() == ErrorKind::Interrupted {
+                continue;
+            }
+            if ready < 0 {
                 break;
             }
-            match c.unwrap() {
-                Key::Char(c) =>
-                    {
-                        send_key.send(c).unwrap();
-                        thread::sleep(Duration::from_millis(10));
-                    },
-                _ => {}
+            if ready == 0 {
+                continue;
             }
-
-            if !keyboard_thread_run.load(Ordering::Relaxed) {
+            let event = match events.next() {
+                Some(Ok(Event::Key(key))) => {
+                    GameEvent::Key(key, shown_offer.get(), Instant::now())
+                }
+                Some(Ok(Event::Mouse(MouseEvent::Press(MouseButton::Left, column, row)))) => {
+                    GameEvent::Click(column, row, shown_offer.get(), Instant::now())
+                }
+                Some(Ok(_)) => {
+                    continue;
+                }
+                Some(Err(_)) | None => {
+                    break;
+                }
+            };
+            if send_event.send(event).is_err() {
                 break;
             }
         }
@@ -1,4 +1,4 @@
-use std::time::SystemTime;
+use std::time::{Duration, Instant};
 
 use crate::console::input::PlayerInteractions;
 use crate::config::Config;
@@ -6,14 +6,16 @@ use crate::config::Config;
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub(crate) struct KeyHeld {
     last_interaction :PlayerInteractions,
-    started: SystemTime,
-    last: SystemTime,
+    started: Instant,
+    last: Instant,
     offer_id: u32
 }
 
 /// Keep track of the last interaction which the player performed and use this to determine if the
 /// current interaction should be performed. This keeps the player from performing actions towards
 /// newest offer by mistake when old offer expires.
+/// The controller is told the time instead of reading the clock, so simulated games can hold keys
+/// on a fake clock
 pub(crate) struct KeyHeldController{
     current: Option<KeyHeld>
 }
@@ -24,35 +26,33 @@ impl KeyHeldController{
     }
 
     /// Will release last performed action if key is not detected in duration determined by config
-    pub(crate) fn cancel_if_elapsed(&mut self) -> bool {
+    pub(crate) fn cancel_if_elapsed(&mut self, now :Instant) -> bool {
         match &self.current{
-            None => {}
-            Some(c) => {
-                if c.can_be_canceled() {
-                    self.current = None;
-                    return true;
-                }
+            Some(c) if c.can_be_canceled(now) => {
+                self.current = None;
+                return true;
             }
+            _ => {}
         }
         return false;
     }
 
-    /// Check if newest interaction should be performed. Returns None if last action was 'buy' or 'sell' and was started on a former offer
-    pub(crate) fn filter_option_interaction(&mut self, interaction :Option<PlayerInteractions>, offer :u32) ->Option<PlayerInteractions> {
+    /// Check if newest interaction, performed at now, should be performed. Returns None if last action was 'buy' or 'sell' and was started on a former offer
+    pub(crate) fn filter_option_interaction(&mut self, interaction :Option<PlayerInteractions>, offer :u32, now :Instant) ->Option<PlayerInteractions> {
         return match interaction {
             None => { None }
             Some(val) => {
-                self.filter_interaction(val, offer)
+                self.filter_interaction(val, offer, now)
             }
         }
     }
 
-    fn filter_interaction(&mut self, interaction :PlayerInteractions, offer :u32) ->Option<PlayerInteractions> {
+    fn filter_interaction(&mut self, interaction :PlayerInteractions, offer :u32, now :Instant) ->Option<PlayerInteractions> {
         if let Some(ref mut current) = self.current{
             if current.last_interaction == interaction &&
                 (interaction == PlayerInteractions::Buy ||
                     interaction == PlayerInteractions::Sell){
-                current.update_last();
+                current.update_last(now);
                 if current.offer_id == offer {
                     return Some(interaction);
                 }else{
@@ -64,8 +64,8 @@ impl KeyHeldController{
 
         self.current = Some(KeyHeld{
             last_interaction: interaction.clone(),
-            started: SystemTime::now(),
-            last: SystemTime::now(),
+            started: now,
+            last: now,
             offer_id: offer
         });
 
@@ -75,20 +75,12 @@ impl KeyHeldController{
 
 impl KeyHeld {
     /// update last time key was detected pressed
-    fn update_last(&mut self){
-        self.last = SystemTime::now();
+    fn update_last(&mut self, now :Instant){
+        self.last = now;
     }
 
-    /// check if key has expired and can be removed. The time key is held will vary between OS and configuration
-    fn can_be_canceled(&self)->bool{
-        let duration = self.last.elapsed();
-        match duration{
-            Ok(dur) => {
-                dur.as_millis() > Config::KEY_RELEASE_MILLIS as u128
-            }
-            Err(_) => {
-                false
-            }
-        }
+    /// check if key has expired at now and can be removed. The time key is held will vary between OS and configuration
+    fn can_be_canceled(&self, now :Instant)->bool{
+        return now.saturating_duration_since(self.last) > Duration::from_millis(Config::KEY_RELEASE_MILLIS as u64);
     }
 }
\ No newline at end of file
@@ -0,0 +1,268 @@
+use strum::IntoEnumIterator;
+use termion::event::Key;
+
+use crate::config::file::ConfigFile;
+use crate::console::input::PlayerInteractions;
+use crate::game::error::GameError;
+
+/// Keys bound to each player interaction. The defaults can be changed in the [keys] section of the
+/// config file, an interaction listed there is bound to the listed keys instead of its default keys.
+/// Keys are written as a character, a name such as Up, Enter, Space or F5, or a modifier followed by
+/// a character such as Ctrl-c or Alt-x.
+/// # Examples
+/// '''
+/// [keys]
+/// buy = b, Up
+/// next_offer = n, Right
+/// '''
+#[derive(Clone, Debug)]
+pub(crate) struct KeyMap {
+    bindings: Vec<(PlayerInteractions, Vec<Key>)>
+}
+
+impl KeyMap {
+    /// Keys the game is played with when the config file does not bind them
+    pub(crate) fn new() -> KeyMap {
+        let bindings = PlayerInteractions::iter()
+            .map(|interaction| {
+                let keys = match interaction{
+                    PlayerInteractions::Buy => { vec![Key::Char('b')] }
+                    PlayerInteractions::Sell => { vec![Key::Char('s')] }
+                    PlayerInteractions::NextOffer => { vec![Key::Char('n')] }
+                    PlayerInteractions::Exit => { vec![Key::Char('e'), Key::Char('q'), Key::Char('c'), Key::Ctrl('c')] }
+                    PlayerInteractions::Info => { vec![Key::Char('i')] }
+                    PlayerInteractions::Help => { vec![Key::Char('?')] }
+                    PlayerInteractions::Pause => { vec![Key::Char('p')] }
+                    PlayerInteractions::Charts => { vec![Key::Char('h')] }
+                    PlayerInteractions::Advisor => { vec![Key::Char('a')] }
+                    PlayerInteractions::Export => { vec![Key::Char('x')] }
+                };
+                (interaction, keys)
+            })
+            .collect();
+        return KeyMap { bindings };
+    }
+
+    /// Default keys with the bindings of the config file's [keys] section applied. Returns an error
+    /// if a binding can not be read, a key is bound to more than one interaction or no key is left
+    /// to end the game with
+    pub(crate) fn from_config(config :&ConfigFile) -> Result<KeyMap, GameError> {
+        let mut key_map = KeyMap::new();
+        for entry in config.section("keys") {
+            let interaction = PlayerInteractions::iter()
+                .find(|interaction| interaction.get_name() == entry.name)
+                .ok_or_else(|| config.error(Some(entry.line), &format!("unknown interaction '{}'", entry.name)))?;
+            let mut keys = Vec::new();
+            for name in entry.value.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+                let key = parse_key(name)
+                    .ok_or_else(|| config.error(Some(entry.line), &format!("unknown key '{}'", name)))?;
+                if !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+            if let Some((_, bound)) = key_map.bindings.iter_mut().find(|(bound, _)| *bound == interaction) {
+                *bound = keys;
+            }
+        }
+        key_map.check().map_err(|message| config.error(None, &message))?;
+        return Ok(key_map);
+    }
+
+    /// Returns an error naming the first key bound to two interactions, or if the game can not be ended
+    fn check(&self) -> Result<(), String> {
+        for (index, (interaction, keys)) in self.bindings.iter().enumerate() {
+            for (other, other_keys) in &self.bindings[index + 1..] {
+                if let Some(key) = keys.iter().find(|key| other_keys.contains(key)) {
+                    return Err(format!("key '{}' is bound to both {} and {}",
+                                       key_name(key), interaction.get_name(), other.get_name()));
+                }
+            }
+        }
+        if self.get_keys(&PlayerInteractions::Exit).is_empty() {
+            return Err(format!("no key is bound to {}", PlayerInteractions::Exit.get_name()));
+        }
+        return Ok(());
+    }
+
+    /// Returns the interaction bound to the key, None if the key is not bound
+    pub(crate) fn get_interaction(&self, key :&Key) -> Option<PlayerInteractions> {
+        return self.bindings.iter()
+            .find(|(_, keys)| keys.contains(key))
+            .map(|(interaction, _)| interaction.clone());
+    }
+
+    /// Keys bound to the interaction, the first one being the one shown in help texts
+    pub(crate) fn get_keys(&self, interaction :&PlayerInteractions) -> &[Key] {
+        return self.bindings.iter()
+            .find(|(bound, _)| bound == interaction)
+            .map(|(_, keys)| keys.as_slice())
+            .unwrap_or(&[]);
+    }
+
+    /// Name of the first key bound to the interaction as written in the config file, None if no key
+    /// is bound to it
+    pub(crate) fn get_key_name(&self, interaction :&PlayerInteractions) -> Option<String> {
+        return self.get_keys(interaction).first().map(key_name);
+    }
+}
+
+/// Returns the key written as in the config file, None if the name is no key
+pub(crate) fn parse_key(name :&str) -> Option<Key> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(Key::Char(c));
+    }
+    let lower = name.to_lowercase();
+    if let Some(c) = single_char(lower.strip_prefix("ctrl-")) {
+        return Some(Key::Ctrl(c));
+    }
+    if let Some(c) = single_char(name.get(4..).filter(|_| lower.starts_with("alt-"))) {
+        return Some(Key::Alt(c));
+    }
+    if let Some(number) = lower.strip_prefix('f').and_then(|number| number.parse::<u8>().ok()) {
+        return Some(Key::F(number));
+    }
+    let key = match lower.as_str(){
+        "up" => { Key::Up }
+        "down" => { Key::Down }
+        "left" => { Key::Left }
+        "right" => { Key::Right }
+        "home" => { Key::Home }
+        "end" => { Key::End }
+        "pageup" => { Key::PageUp }
+        "pagedown" => { Key::PageDown }
+        "backspace" => { Key::Backspace }
+        "delete" => { Key::Delete }
+        "insert" => { Key::Insert }
+        "esc" => { Key::Esc }
+        "backtab" => { Key::BackTab }
+        "tab" => { Key::Char('\t') }
+        "enter" => { Key::Char('\n') }
+        "space" => { Key::Char(' ') }
+        _ => { return None; }
+    };
+    return Some(key);
+}
+
+/// Returns the only character of the text, None if the text is missing or not a single character
+fn single_char(text :Option<&str>) -> Option<char> {
+    let mut chars = text?.chars();
+    return match (chars.next(), chars.next()) {
+        (Some(c), None) => { Some(c) }
+        _ => { None }
+    };
+}
+
+/// Returns the name of the key as written in the config file
+pub(crate) fn key_name(key :&Key) -> String {
+    return match key{
+        Key::Char(' ') => { String::from("Space") }
+        Key::Char('\n') => { String::from("Enter") }
+        Key::Char('\t') => { String::from("Tab") }
+        Key::Char(c) => { c.to_string() }
+        Key::Ctrl(c) => { format!("Ctrl-{}", c) }
+        Key::Alt(c) => { format!("Alt-{}", c) }
+        Key::F(number) => { format!("F{}", number) }
+        Key::Up => { String::from("Up") }
+        Key::Down => { String::from("Down") }
+        Key::Left => { String::from("Left") }
+        Key::Right => { String::from("Right") }
+        Key::Home => { String::from("Home") }
+        Key::End => { String::from("End") }
+        Key::PageUp => { String::from("PageUp") }
+        Key::PageDown => { String::from("PageDown") }
+        Key::Backspace => { String::from("Backspace") }
+        Key::Delete => { String::from("Delete") }
+        Key::Insert => { String::from("Insert") }
+        Key::Esc => { String::from("Esc") }
+        Key::BackTab => { String::from("BackTab") }
+        _ => { String::from("?") }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_map(text :&str) -> Result<KeyMap, GameError> {
+        return KeyMap::from_config(&ConfigFile::parse("test.conf", text).unwrap());
+    }
+
+    fn error(result :Result<KeyMap, GameError>) -> String {
+        match result {
+            Ok(_) => { panic!("key map should not load") }
+            Err(GameError::InvalidConfig(message)) => { message }
+            Err(error) => { panic!("unexpected error {}", error) }
+        }
+    }
+
+    #[test]
+    fn parses_characters_names_and_modifiers() {
+        assert_eq!(parse_key("b"), Some(Key::Char('b')));
+        assert_eq!(parse_key("?"), Some(Key::Char('?')));
+        assert_eq!(parse_key("Up"), Some(Key::Up));
+        assert_eq!(parse_key("pagedown"), Some(Key::PageDown));
+        assert_eq!(parse_key("Space"), Some(Key::Char(' ')));
+        assert_eq!(parse_key("ENTER"), Some(Key::Char('\n')));
+        assert_eq!(parse_key("F5"), Some(Key::F(5)));
+        assert_eq!(parse_key("Ctrl-c"), Some(Key::Ctrl('c')));
+        assert_eq!(parse_key("ctrl-C"), Some(Key::Ctrl('c')));
+        assert_eq!(parse_key("Alt-X"), Some(Key::Alt('X')));
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        for name in ["", "Foo", "Ctrl-", "Ctrl-ab", "Alt-", "Shift-x", "F300", "Fx"] {
+            assert_eq!(parse_key(name), None, "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn key_names_parse_back_to_the_key() {
+        for (_, keys) in &KeyMap::new().bindings {
+            for key in keys {
+                assert_eq!(parse_key(&key_name(key)).as_ref(), Some(key));
+            }
+        }
+        assert_eq!(parse_key(&key_name(&Key::Char(' '))), Some(Key::Char(' ')));
+        assert_eq!(parse_key(&key_name(&Key::F(12))), Some(Key::F(12)));
+    }
+
+    #[test]
+    fn default_keys_do_not_conflict() {
+        assert!(KeyMap::new().check().is_ok());
+        assert!(key_map("").is_ok());
+    }
+
+    #[test]
+    fn config_replaces_the_default_keys() {
+        let key_map = key_map("[keys]\nbuy = Up, k, Up\nsell = b\n").unwrap();
+        assert_eq!(key_map.get_keys(&PlayerInteractions::Buy), &[Key::Up, Key::Char('k')]);
+        assert_eq!(key_map.get_interaction(&Key::Char('b')), Some(PlayerInteractions::Sell));
+        assert_eq!(key_map.get_interaction(&Key::Char('s')), None);
+        assert_eq!(key_map.get_key_name(&PlayerInteractions::Buy), Some(String::from("Up")));
+        assert_eq!(key_map.get_interaction(&Key::Char('n')), Some(PlayerInteractions::NextOffer));
+    }
+
+    #[test]
+    fn conflicting_keys_are_rejected() {
+        let message = error(key_map("[keys]\nbuy = s\n"));
+        assert_eq!(message, "test.conf: key 's' is bound to both buy and sell");
+        let message = error(key_map("[keys]\ninfo = Up\ncharts = Up\n"));
+        assert_eq!(message, "test.conf: key 'Up' is bound to both info and charts");
+    }
+
+    #[test]
+    fn game_must_be_endable() {
+        let message = error(key_map("[keys]\nexit =\n"));
+        assert_eq!(message, "test.conf: no key is bound to exit");
+    }
+
+    #[test]
+    fn unknown_interactions_and_keys_name_their_line() {
+        let message = error(key_map("[keys]\nbuy = b\njump = j\n"));
+        assert_eq!(message, "test.conf: line 3: unknown interaction 'jump'");
+        let message = error(key_map("[keys]\nbuy = Hyper-b\n"));
+        assert_eq!(message, "test.conf: line 2: unknown key 'Hyper-b'");
+    }
+}
@@ -0,0 +1,361 @@
+use std::ops::Range;
+
+use termion::style;
+use unicode_width::UnicodeWidthChar;
+
+use crate::config::Config;
+
+/// Clickable area of a single row, columns counted in plain text from 0. Within a panel the row is
+/// the index of the panel's line, on the screen it is the row of the screen, see compose. The id
+/// tells the panel's owner which button was clicked
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Button {
+    pub(crate) row: usize,
+    pub(crate) columns: Range<usize>,
+    pub(crate) id: usize
+}
+
+/// Rows of the game screen and the buttons shown on it
+pub(crate) struct Screen {
+    pub(crate) rows: Vec<String>,
+    pub(crate) buttons: Vec<Button>
+}
+
+/// Titled box of lines on the game screen. Fixed panels are always shown with all their lines,
+/// growing panels share the rows the fixed panels leave over and show as many of their last lines
+/// as fit, or their first lines for text which is read from the top.
+/// Lines of a panel can hold buttons, which are only clickable while their line is shown.
+pub(crate) struct Panel {
+    title: Option<String>,
+    lines: Vec<String>,
+    buttons: Vec<Button>,
+    grow: bool,
+    from_top: bool
+}
+
+impl Panel {
+    /// Panel showing all of its lines
+    pub(crate) fn fixed(title :Option<&str>, lines :Vec<String>) -> Panel {
+        return Panel { title: title.map(String::from), lines, buttons: Vec::new(), grow: false, from_top: false };
+    }
+
+    /// Panel showing the last of its lines which fit in the rows left over
+    pub(crate) fn growing(title :&str, lines :Vec<String>) -> Panel {
+        return Panel { title: Some(String::from(title)), lines, buttons: Vec::new(), grow: true, from_top: false };
+    }
+
+    /// Growing panel showing the first of its lines which fit in the rows left over
+    pub(crate) fn text(title :&str, lines :Vec<String>) -> Panel {
+        return Panel { title: Some(String::from(title)), lines, buttons: Vec::new(), grow: true, from_top: true };
+    }
+
+    /// Panel with buttons on its lines, the buttons' rows being indices of the panel's lines
+    pub(crate) fn with_buttons(mut self, buttons :Vec<Button>) -> Panel {
+        self.buttons = buttons;
+        return self;
+    }
+
+    fn title_rows(&self) -> usize {
+        return if self.title.is_some() { 1 } else { 0 };
+    }
+
+    /// Rows needed to show all lines
+    fn height(&self) -> usize {
+        return self.title_rows() + self.lines.len();
+    }
+
+    /// Index of the line shown on row of the panel when it is given rows rows, None for the title
+    fn line_index(&self, row :usize, rows :usize) -> Option<usize> {
+        if row < self.title_rows() {
+            return None;
+        }
+        let shown = rows - self.title_rows();
+        let first = if self.from_top { 0 } else { self.lines.len().saturating_sub(shown) };
+        return Some(first + row - self.title_rows());
+    }
+
+    /// Returns row of the panel when it is given rows rows, fitted to width
+    fn row(&self, row :usize, rows :usize, width :usize) -> String {
+        let line = match (&self.title, self.line_index(row, rows)) {
+            (Some(title), None) => { return title_bar(title, width); }
+            (_, Some(index)) => { self.lines.get(index).map(String::as_str).unwrap_or("") }
+            (None, None) => { "" }
+        };
+        return fit(line, width);
+    }
+
+    /// Returns the buttons on row of the panel when it is given rows rows, placed on screen_row
+    /// with the panel starting at column left and cut to width
+    fn buttons(&self, row :usize, rows :usize, screen_row :usize, left :usize, width :usize) -> Vec<Button> {
+        let index = match self.line_index(row, rows) {
+            Some(index) => { index }
+            None => { return Vec::new(); }
+        };
+        return self.buttons.iter()
+            .filter(|button| button.row == index && button.columns.start < width)
+            .map(|button| Button {
+                row: screen_row,
+                columns: left + button.columns.start..left + button.columns.end.min(width),
+                id: button.id
+            })
+            .collect();
+    }
+}
+
+/// Row of the game screen holding either one panel or two panels beside each other. Two panels
+/// are only placed beside each other when the terminal is wide enough, else below each other.
+pub(crate) enum Section {
+    Full(Panel),
+    Split(Panel, Panel)
+}
+
+/// Panels which made it onto the screen with the rows given to them
+struct Block<'a> {
+    panels: Vec<&'a Panel>,
+    rows: usize
+}
+
+/// Arrange the sections top to bottom on a screen of width by height and return the screen's rows
+/// with the buttons which made it onto the screen.
+/// Fixed panels are placed in order as long as they fit, panels which do not fit are left out.
+/// The rows left over are split evenly between the growing panels, a growing panel needs room for
+/// its title and at least one line to be shown.
+pub(crate) fn compose(sections :&[Section], width :u16, height :u16) -> Screen {
+    let width = width as usize;
+    let height = height as usize;
+    let split = width >= Config::SPLIT_PANELS_MIN_WIDTH as usize;
+
+    let mut blocks: Vec<Block> = Vec::new();
+    for section in sections {
+        match section {
+            Section::Split(left, right) if split => {
+                blocks.push(Block { panels: vec![left, right], rows: 0 });
+            }
+            Section::Split(top, bottom) => {
+                blocks.push(Block { panels: vec![top], rows: 0 });
+                blocks.push(Block { panels: vec![bottom], rows: 0 });
+            }
+            Section::Full(panel) => {
+                blocks.push(Block { panels: vec![panel], rows: 0 });
+            }
+        }
+    }
+
+    //Blocks grow only when all of their panels grow, a growing panel beside a fixed one takes the
+    //fixed panel's height
+    let grows = |block :&Block| block.panels.iter().all(|panel| panel.grow);
+    let mut left = height;
+    for block in blocks.iter_mut().filter(|block| !grows(block)) {
+        let rows = block.panels.iter().filter(|panel| !panel.grow).map(|panel| panel.height()).max().unwrap_or(0);
+        if rows <= left {
+            block.rows = rows;
+            left -= rows;
+        }
+    }
+    let mut growing = blocks.iter().filter(|block| grows(block)).count();
+    for block in blocks.iter_mut().filter(|block| grows(block)) {
+        let rows = left / growing;
+        growing -= 1;
+        if rows >= 2 {
+            block.rows = rows;
+            left -= rows;
+        }
+    }
+
+    let mut screen: Vec<String> = Vec::with_capacity(height);
+    let mut buttons: Vec<Button> = Vec::new();
+    for block in blocks.iter().filter(|block| block.rows > 0) {
+        for row in 0..block.rows {
+            let screen_row = screen.len();
+            match block.panels.as_slice() {
+                [left, right] => {
+                    let left_width = width / 2;
+                    let right_width = width - left_width - 1;
+                    screen.push(format!("{}│{}",
+                                        left.row(row, block.rows, left_width),
+                                        right.row(row, block.rows, right_width)));
+                    buttons.extend(left.buttons(row, block.rows, screen_row, 0, left_width));
+                    buttons.extend(right.buttons(row, block.rows, screen_row, left_width + 1, right_width));
+                }
+                panels => {
+                    screen.push(panels[0].row(row, block.rows, width));
+                    buttons.extend(panels[0].buttons(row, block.rows, screen_row, 0, width));
+                }
+            }
+        }
+    }
+    while screen.len() < height {
+        screen.push(" ".repeat(width));
+    }
+    return Screen { rows: screen, buttons };
+}
+
+/// Returns a bar per value, the bars' heights scaled between the lowest and highest value. Bars
+/// are of medium height when all values are the same
+pub(crate) fn sparkline(values :&[f64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let min = values.iter().cloned().fold(f64::MAX, f64::min);
+    let max = values.iter().cloned().fold(f64::MIN, f64::max);
+    return values.iter()
+        .map(|value| {
+            if max <= min {
+                return BARS[3];
+            }
+            return BARS[((value - min) / (max - min) * (BARS.len() - 1) as f64).round() as usize];
+        })
+        .collect();
+}
+
+/// Break plain text into lines of at most width columns at spaces, words longer than the width are
+/// left for fit to cut
+pub(crate) fn wrap(text :&str, width :usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && self::width(&line) + 1 + self::width(word) > width {
+            lines.push(line);
+            line = String::new();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    lines.push(line);
+    return lines;
+}
+
+/// Returns title bar '── title ───' filling the width
+fn title_bar(title :&str, width :usize) -> String {
+    let bar = format!("── {bold}{title}{reset} {line}",
+                      bold = style::Bold,
+                      title = title,
+                      reset = style::Reset,
+                      line = "─".repeat(width));
+    return fit(&bar, width);
+}
+
+/// Cut text to width columns, or pad it with spaces to width columns. Styles are reset at the end
+/// so colors do not bleed into the next panel. A wide character which would not fit whole is cut
+pub(crate) fn fit(text :&str, width :usize) -> String {
+    let mut fitted = String::with_capacity(text.len() + width);
+    let mut columns = 0;
+    let mut cut = false;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            //Escape sequences are kept whole even after the text is cut so styles stay balanced
+            fitted.push(c);
+            fitted.push_str(&read_escape(&mut chars));
+            continue;
+        }
+        let char_width = c.width().unwrap_or(0);
+        cut |= columns + char_width > width;
+        if !cut {
+            fitted.push(c);
+            columns += char_width;
+        }
+    }
+    fitted.push_str(&" ".repeat(width - columns));
+    fitted.push_str(style::Reset.as_ref());
+    return fitted;
+}
+
+/// Pad text with spaces to width columns, text which is wider is left as is
+pub(crate) fn pad(text :&str, width :usize) -> String {
+    return format!("{}{}", text, " ".repeat(width.saturating_sub(self::width(text))));
+}
+
+/// Columns the text takes up on the terminal, wide characters such as CJK taking two columns and
+/// escape sequences none
+pub(crate) fn width(text :&str) -> usize {
+    return plain(text).chars().map(|c| c.width().unwrap_or(0)).sum();
+}
+
+/// Returns the text without its escape sequences, leaving plain text for screen readers
+pub(crate) fn plain(text :&str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            read_escape(&mut chars);
+        }else{
+            plain.push(c);
+        }
+    }
+    return plain;
+}
+
+/// Consume the rest of a CSI escape sequence after its escape character and return it
+fn read_escape(chars :&mut std::str::Chars) -> String {
+    let mut sequence = String::new();
+    for c in chars {
+        sequence.push(c);
+        if c != '[' && ('\x40'..='\x7e').contains(&c) {
+            break;
+        }
+    }
+    return sequence;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sections() -> Vec<Section> {
+        return vec![
+            Section::Full(Panel::fixed(None, vec![String::from("FRUIT STORE")])),
+            Section::Split(Panel::fixed(Some("Offer"), vec![String::from("Grade A りんご for 2.50$")]),
+                           Panel::fixed(Some("Charts"), vec![String::from("りんご ▁▃▅█")])),
+            Section::Split(Panel::growing("Events", (0..20).map(|line| format!("event {}", line)).collect()),
+                           Panel::growing("History", vec![String::from("  1 ドラゴンフルーツ A 3.00$")])),
+            Section::Full(Panel::fixed(Some("Keys"), vec![String::from("[b] buy")]).with_buttons(vec![Button { row: 0, columns: 0..7, id: 1 }]))
+        ];
+    }
+
+    #[test]
+    fn wide_characters_take_two_columns() {
+        assert_eq!(width("りんご"), 6);
+        assert_eq!(width("\x1b[1mApple\x1b[m"), 5);
+        assert_eq!(plain(&fit("りんご", 5)), "りん ");
+        assert_eq!(plain(&fit("\x1b[1mりんご", 8)), "りんご  ");
+        assert_eq!(pad("りんご", 8), "りんご  ");
+    }
+
+    #[test]
+    fn wrap_counts_columns() {
+        assert_eq!(wrap("りんご and バナナ", 10), vec!["りんご and", "バナナ"]);
+    }
+
+    #[test]
+    fn narrow_terminal_stacks_split_panels() {
+        let screen = compose(&sections(), 40, 20);
+        assert_eq!(screen.rows.len(), 20);
+        assert!(screen.rows.iter().all(|row| width(row) == 40), "{:?}", screen.rows);
+        assert!(screen.rows.iter().all(|row| !row.contains('│')));
+        assert!(plain(&screen.rows[1]).starts_with("── Offer"));
+        assert!(plain(&screen.rows[3]).starts_with("── Charts"));
+        assert_eq!(screen.buttons, vec![Button { row: 19, columns: 0..7, id: 1 }]);
+    }
+
+    #[test]
+    fn wide_terminal_places_split_panels_side_by_side() {
+        let screen = compose(&sections(), 100, 20);
+        assert_eq!(screen.rows.len(), 20);
+        assert!(screen.rows.iter().all(|row| width(row) == 100), "{:?}", screen.rows);
+        let offer = plain(&screen.rows[2]);
+        assert!(offer.starts_with("Grade A りんご"));
+        assert_eq!(offer.chars().position(|c| c == '│').map(|column| width(&offer.chars().take(column).collect::<String>())), Some(50));
+        //Growing panels share the rows left over and show their last lines
+        assert!(screen.rows.iter().any(|row| plain(row).contains("event 19")));
+        assert_eq!(screen.buttons, vec![Button { row: 19, columns: 0..7, id: 1 }]);
+    }
+
+    #[test]
+    fn terminal_too_small_leaves_panels_out() {
+        let screen = compose(&sections(), 100, 4);
+        assert_eq!(screen.rows.len(), 4);
+        assert!(screen.rows.iter().all(|row| !plain(row).contains("Keys")));
+        assert!(screen.buttons.is_empty());
+    }
+}
@@ -0,0 +1,349 @@
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::io::ErrorKind;
+
+use termion::style;
+
+use crate::config::Config;
+use crate::config::file::ConfigFile;
+use crate::console::input::PlayerInteractions;
+use crate::fruit::Fruit;
+use crate::fruit::season::Season;
+use crate::game::error::GameError;
+use crate::game::money::Money;
+
+/// English catalog built into the game, every other catalog falls back to it
+const ENGLISH: &str = include_str!("../../lang/en.ini");
+
+/// Texts of the game in one language, read from a message catalog in the lang directory. A catalog
+/// is written like the config file, its sections holding the messages, the names of fruits,
+/// seasons and interactions, market event headlines, tutorial prompts and how money is written.
+/// Messages a catalog does not translate are taken from the English catalog. The currency symbol
+/// and name of the catalog can be replaced in the config file's [currency] section.
+/// # Examples
+/// '''
+/// [fruits]
+/// apple = Apfel
+///
+/// [messages]
+/// skipping_turn = Runde übersprungen
+/// '''
+#[derive(Clone, Debug)]
+pub(crate) struct Locale {
+    texts: HashMap<(String, String), String>
+}
+
+impl Locale {
+    /// Locale with the English catalog only
+    pub(crate) fn english() -> Locale {
+        let mut locale = Locale { texts: HashMap::new() };
+        let catalog = ConfigFile::parse("lang/en.ini", ENGLISH).expect("built in English catalog parses");
+        locale.add(&catalog);
+        return locale;
+    }
+
+    /// Locale of the language, or of the language the environment's LC_ALL, LC_MESSAGES or LANG
+    /// asks for if none is given. The catalog is read from Config::LANG_DIR, or the directory named
+    /// by the FRUITSTORE_LANG_DIR environment variable. A missing catalog for the environment's
+    /// language leaves the game in English, a missing catalog for a language which was given is
+    /// an error. Returns an error if a setting of the config file's [currency] section can not be read
+    pub(crate) fn load(lang :Option<&str>, config :&ConfigFile) -> Result<Locale, GameError> {
+        let mut locale = Locale::english();
+        let (lang, explicit) = match lang {
+            Some(lang) => { (lang.to_lowercase(), true) }
+            None => { (detect_language(), false) }
+        };
+        if lang == "en" || (!explicit && lang.is_empty()) {
+            locale.set_currency(config)?;
+            return Ok(locale);
+        }
+        if lang.is_empty() || !lang.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(GameError::InvalidArgument(format!("--lang {}", lang)));
+        }
+        let dir = env::var(Config::LANG_DIR_ENV).unwrap_or_else(|_| String::from(Config::LANG_DIR));
+        let path = format!("{}/{}.ini", dir, lang);
+        match fs::read_to_string(&path) {
+            Ok(text) => {
+                locale.add(&ConfigFile::parse(&path, &text)?);
+            }
+            Err(error) if error.kind() == ErrorKind::NotFound && !explicit => {}
+            Err(error) => {
+                return Err(GameError::InvalidConfig(format!("{}: {}", path, error)));
+            }
+        }
+        locale.set_currency(config)?;
+        return Ok(locale);
+    }
+
+    /// Replace the currency symbol and name with those of the config file's [currency] section
+    /// # Examples
+    /// '''
+    /// [currency]
+    /// symbol = £
+    /// name = pounds
+    /// '''
+    fn set_currency(&mut self, config :&ConfigFile) -> Result<(), GameError> {
+        for entry in config.section("currency") {
+            match entry.name.as_str(){
+                "symbol" | "name" => {
+                    self.texts.insert((String::from("currency"), entry.name.clone()), entry.value.clone());
+                }
+                _ => {
+                    return Err(config.error(Some(entry.line), &format!("unknown currency setting '{}', expected symbol or name", entry.name)));
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    /// Add the catalog's texts, replacing texts which were added before
+    fn add(&mut self, catalog :&ConfigFile) {
+        for section in ["currency", "fruits", "seasons", "interactions", "events", "tutorial", "messages"] {
+            for entry in catalog.section(section) {
+                self.texts.insert((entry.section.clone(), entry.name.clone()), entry.value.clone());
+            }
+        }
+    }
+
+    /// Text of the catalog's section, the name itself if no catalog has the text so missing texts
+    /// show up in game
+    fn get<'a>(&'a self, section :&str, name :&'a str) -> &'a str {
+        return self.texts.get(&(String::from(section), String::from(name)))
+            .map(String::as_str)
+            .unwrap_or(name);
+    }
+
+    /// Message with each {name} replaced by the value given for it, see fill
+    pub(crate) fn text(&self, name :&str, args :&[(&str, &dyn Display)]) -> String {
+        return fill(self.get("messages", name), args);
+    }
+
+    pub(crate) fn fruit(&self, fruit :&Fruit) -> &str {
+        return self.get("fruits", fruit.get_key());
+    }
+
+    pub(crate) fn season(&self, season :&Season) -> &str {
+        return self.get("seasons", season.get_key());
+    }
+
+    /// What the interaction does, as shown in the keys panel
+    pub(crate) fn interaction(&self, interaction :&PlayerInteractions) -> &str {
+        return self.get("interactions", interaction.get_name());
+    }
+
+    /// Headline of the market event with the name, see Config::market_events
+    pub(crate) fn event(&self, name :&str) -> String {
+        return String::from(self.get("events", name));
+    }
+
+    /// Tutorial prompt of the step with the name, see Config::tutorial_steps
+    pub(crate) fn tutorial(&self, name :&str, args :&[(&str, &dyn Display)]) -> String {
+        return fill(self.get("tutorial", name), args);
+    }
+
+    /// Amount of money written as the catalog's currency format, such as 5$ or 4,50 €. Cents are
+    /// only written for amounts which have them
+    pub(crate) fn money(&self, amount :Money) -> String {
+        return self.currency("format", &self.amount(amount));
+    }
+
+    /// Amount of money with the decimals, written as the catalog's currency format
+    pub(crate) fn money_fraction(&self, amount :f64, decimals :usize) -> String {
+        return self.currency("format", &self.number(amount, decimals));
+    }
+
+    /// Amount of money as announced in screen reader mode, such as 5 dollars
+    pub(crate) fn spoken_money(&self, amount :Money) -> String {
+        return self.currency("spoken", &self.amount(amount));
+    }
+
+    /// Amount of money with the decimals as announced in screen reader mode
+    pub(crate) fn spoken_money_fraction(&self, amount :f64, decimals :usize) -> String {
+        return self.currency("spoken", &self.number(amount, decimals));
+    }
+
+    /// Amount of money as a number counted in whole cents, the cents only written for amounts
+    /// which have them
+    fn amount(&self, amount :Money) -> String {
+        let cents = amount.get_cents();
+        let fraction = if amount.is_whole() { String::new() } else { format!("{:02}", cents % 100) };
+        return self.separate(&(cents / 100).to_string(), &fraction);
+    }
+
+    /// Written amount filled into the currency section's format with the name, with the currency's
+    /// symbol and name
+    fn currency(&self, name :&str, amount :&str) -> String {
+        return fill(self.get("currency", name), &[
+            ("amount", &amount),
+            ("symbol", &self.get("currency", "symbol")),
+            ("name", &self.get("currency", "name"))]);
+    }
+
+    /// Number with the decimals, using the catalog's decimal and thousands separators
+    pub(crate) fn number(&self, number :f64, decimals :usize) -> String {
+        let formatted = format!("{:.*}", decimals, number);
+        let (whole, fraction) = formatted.split_once('.').unwrap_or((&formatted, ""));
+        return self.separate(whole, fraction);
+    }
+
+    /// Whole part and fraction of a number joined by the catalog's decimal separator, the whole
+    /// part grouped by the thousands separator
+    fn separate(&self, whole :&str, fraction :&str) -> String {
+        let separator = self.get("currency", "thousands_separator");
+        let mut grouped = String::new();
+        for (index, digit) in whole.chars().enumerate() {
+            //Digits are grouped by three counting from the right, never after a sign
+            if (whole.len() - index) % 3 == 0 && grouped.ends_with(|c :char| c.is_ascii_digit()) {
+                grouped.push_str(separator);
+            }
+            grouped.push(digit);
+        }
+        if fraction.is_empty() {
+            return grouped;
+        }
+        return format!("{}{}{}", grouped, self.get("currency", "decimal_separator"), fraction);
+    }
+}
+
+/// Returns the text with each {name} replaced by the value given for it, {bold} and {reset} by the
+/// styles starting and ending bold text. Names without a value are left as they are
+fn fill(text :&str, args :&[(&str, &dyn Display)]) -> String {
+    let mut filled = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let name = match rest.find('}') {
+            Some(end) => { &rest[1..end] }
+            None => { break; }
+        };
+        let value = match name {
+            "bold" => { Some(style::Bold.to_string()) }
+            "reset" => { Some(style::Reset.to_string()) }
+            _ => { args.iter().find(|(arg, _)| *arg == name).map(|(_, value)| value.to_string()) }
+        };
+        match value {
+            Some(value) => { filled.push_str(&value); }
+            None => { filled.push_str(&rest[..name.len() + 2]); }
+        }
+        rest = &rest[name.len() + 2..];
+    }
+    filled.push_str(rest);
+    return filled;
+}
+
+/// Language the environment asks for, such as de for LANG=de_DE.UTF-8. Returns en for the C and
+/// POSIX locales and an empty string if no language is set
+fn detect_language() -> String {
+    for variable in ["LC_ALL", "LC_MESSAGES", "LANG"] {
+        let value = env::var(variable).unwrap_or_default();
+        let lang = value.split(['_', '.', '@']).next().unwrap_or("").to_lowercase();
+        if lang == "c" || lang == "posix" {
+            return String::from("en");
+        }
+        if !lang.is_empty() {
+            return lang;
+        }
+    }
+    return String::new();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// English locale with the catalog's texts added on top, as Locale::load does
+    fn with_catalog(path :&str, text :&str) -> Locale {
+        let mut locale = Locale::english();
+        locale.add(&ConfigFile::parse(path, text).unwrap());
+        return locale;
+    }
+
+    fn german() -> Locale {
+        return with_catalog("lang/de.ini", include_str!("../../lang/de.ini"));
+    }
+
+    #[test]
+    fn fill_replaces_placeholders_with_their_values() {
+        let filled = fill("{fruit} for {price}, {fruit} again", &[("fruit", &"Apple"), ("price", &5)]);
+        assert_eq!(filled, "Apple for 5, Apple again");
+        assert_eq!(fill("{bold}score{reset}", &[]), format!("{}score{}", style::Bold, style::Reset));
+    }
+
+    #[test]
+    fn fill_leaves_placeholders_without_values() {
+        assert_eq!(fill("{fruit} costs {price}", &[("fruit", &"Apple")]), "Apple costs {price}");
+        assert_eq!(fill("open {fruit", &[("fruit", &"Apple")]), "open {fruit");
+        assert_eq!(fill("", &[("fruit", &"Apple")]), "");
+    }
+
+    #[test]
+    fn numbers_are_grouped_and_rounded() {
+        let english = Locale::english();
+        assert_eq!(english.number(1234567.891, 2), "1234567.89");
+        assert_eq!(english.number(2.5, 0), "2");
+        assert_eq!(english.number(0.125, 2), "0.12");
+        let german = german();
+        assert_eq!(german.number(0.0, 0), "0");
+        assert_eq!(german.number(999.0, 0), "999");
+        assert_eq!(german.number(1000.0, 0), "1.000");
+        assert_eq!(german.number(1234567.891, 2), "1.234.567,89");
+        assert_eq!(german.number(-1234.5, 1), "-1.234,5");
+        assert_eq!(german.number(-123.0, 0), "-123");
+    }
+
+    #[test]
+    fn money_is_written_in_the_catalogs_format() {
+        let english = Locale::english();
+        assert_eq!(english.money(Money::from_dollars(5)), "5$");
+        assert_eq!(english.money(Money::from_cents(123456)), "1234.56$");
+        assert_eq!(english.spoken_money(Money::from_cents(450)), "4.50 dollars");
+        let german = german();
+        assert_eq!(german.money(Money::from_dollars(5)), "5 €");
+        assert_eq!(german.money(Money::from_cents(123456)), "1.234,56 €");
+        assert_eq!(german.spoken_money(Money::from_cents(450)), "4,50 Euro");
+    }
+
+    #[test]
+    fn large_amounts_keep_their_cents() {
+        let amount = Money::from_cents(9_007_199_254_740_993);
+        assert_eq!(Locale::english().money(amount), "90071992547409.93$");
+        assert_eq!(german().money(amount), "90.071.992.547.409,93 €");
+        assert_eq!(german().spoken_money(Money::from_cents(u64::MAX)), "184.467.440.737.095.516,15 Euro");
+    }
+
+    #[test]
+    fn built_in_catalogs_parse() {
+        assert!(ConfigFile::parse("lang/en.ini", ENGLISH).is_ok());
+        assert!(ConfigFile::parse("lang/de.ini", include_str!("../../lang/de.ini")).is_ok());
+    }
+
+    #[test]
+    fn non_english_catalog_translates_texts() {
+        let german = german();
+        assert_eq!(german.fruit(&Fruit::Apples), "Apfel");
+        assert_eq!(german.season(&Season::Spring), "Frühling");
+        assert_eq!(german.text("exported", &[("path", &"a.csv")]), "Transaktionen nach a.csv exportiert");
+    }
+
+    #[test]
+    fn missing_texts_fall_back_to_english_then_to_their_name() {
+        let partial = with_catalog("lang/xx.ini", "[messages]\nskipping_turn = Tour passé\n");
+        assert_eq!(partial.text("skipping_turn", &[]), "Tour passé");
+        assert_eq!(partial.text("exported", &[("path", &"a.csv")]), "Transactions exported to a.csv");
+        assert_eq!(partial.fruit(&Fruit::Apples), "Apple");
+        assert_eq!(partial.text("no_such_message", &[]), "no_such_message");
+    }
+
+    #[test]
+    fn config_file_replaces_the_currency() {
+        let mut locale = german();
+        locale.set_currency(&ConfigFile::parse("test.conf", "[currency]\nsymbol = £\nname = pounds\n").unwrap()).unwrap();
+        assert_eq!(locale.money(Money::from_cents(250)), "2,50 £");
+        assert_eq!(locale.spoken_money(Money::from_cents(250)), "2,50 pounds");
+        let error = locale.set_currency(&ConfigFile::parse("test.conf", "[currency]\nrate = 2\n").unwrap());
+        assert!(matches!(error, Err(GameError::InvalidConfig(_))));
+    }
+}
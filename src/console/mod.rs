@@ -1,3 +1,9 @@
+pub mod accessibility;
 pub mod output;
+pub mod layout;
+pub mod locale;
 pub mod input;
 pub mod key_handling;
+pub mod key_map;
+pub mod terminal;
+pub mod theme;
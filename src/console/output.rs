@@ -1,185 +1,861 @@
-use std::ops::Range;
-use std::io::{Stdout, stdout, Write};
+use std::collections::VecDeque;
+use std::ops::{Range, RangeInclusive};
+use std::io::{stdout, Write};
 use strum::IntoEnumIterator;
-use termion::{style, color, clear};
-use termion::raw::{IntoRawMode, RawTerminal};
+use termion::{style, clear, cursor};
+use termion::raw::IntoRawMode;
 
-use crate::fruit::Fruit;
+use crate::console::accessibility::Verbosity;
+use crate::console::input::PlayerInteractions;
+use crate::console::key_map::{self, KeyMap};
+use crate::console::layout::{self, Button, Panel, Section};
+use crate::console::locale::Locale;
+use crate::console::terminal;
+use crate::console::theme::{Role, Theme};
+
+use crate::fruit::{Fruit, FruitOffer, OfferKind};
+use crate::fruit::auction::{Auction, Bidder};
+use crate::fruit::season::Season;
+use crate::fruit::price_history::PriceHistory;
+use crate::fruit::quality::Quality;
 use crate::config::Config;
-use crate::{Player};
+use crate::player::Player;
+use crate::player::ledger::{Side, Transaction};
+use crate::game::advisor::{Advice, Rating};
+use crate::game::error::GameError;
+use crate::game::money::Money;
 
+/// Game screen drawn full screen as panels, or announced as plain text in screen reader mode
 pub(crate) struct Output {
-    stdout : RawTerminal<Stdout>,
-    has_printed : bool,
-    offers_left: String,
+    //Terminal in raw mode, which is restored when dropped
+    stdout : Box<dyn Write>,
+    key_map: KeyMap,
+    theme: Theme,
+    locale: Locale,
+    size: (u16, u16),
+    drawn: Vec<String>,
+    buttons: Vec<Button>,
+    market: String,
+    news: String,
     offer_timeout: String,
-    offer: String,
-    status: String,
-    player_feedback: String
+    offer: Vec<String>,
+    inventory: Vec<String>,
+    history: Vec<String>,
+    charts: Vec<String>,
+    show_charts: bool,
+    advice: Option<String>,
+    log: VecDeque<String>,
+    transactions: Vec<String>,
+    paused: bool,
+    help: bool,
+    ended: bool,
+    screen_reader: Option<Verbosity>,
+    time_announced: u32,
+    transactions_announced: usize
 }
 
 impl Output {
     ///  Prints the game's intro screen with title and the different fruit price ranges
-    pub(crate) fn print_intro(&mut self){
-        self.println(format!("      ***********************"));
-        self.println(format!("      *     {bold}{red}F{orange}R{yellow}UI{green}T S{cyan}TO{blue}R{purple}E{reset}     *",
+    pub(crate) fn print_intro(&mut self) -> Result<(), GameError> {
+        if self.screen_reader.is_some() {
+            let intro = self.locale.text("intro_keys", &[("keys", &self.keys_line().0)]);
+            return self.announce(Verbosity::Terse, &intro);
+        }
+        self.println(String::from("      ***********************"))?;
+        let title: String = ["F", "R", "UI", "T S", "TO", "R", "E"].iter()
+            .enumerate()
+            .map(|(index, letters)| format!("{}{}", self.theme.fg(Role::Title(index)), letters))
+            .collect();
+        self.println(format!("      *     {bold}{title}{reset}     *",
                              bold  = style::Bold,
-                             red = color::Fg(color::Red),
-                             orange = color::Fg(color::Rgb(255,165,0)),
-                             yellow = color::Fg(color::Yellow),
-                             green = color::Fg(color::Green),
-                             cyan = color::Fg(color::Rgb(0,255,255)),
-                             blue = color::Fg(color::Blue),
-                             purple = color::Fg(color::Rgb(128,0,128)),
-                             reset = style::Reset));
-        self.println(format!("      ***********************"));
+                             title = title,
+                             reset = style::Reset))?;
+        self.println(String::from("      ***********************"))?;
         for fruit in Fruit::iter(){
-            let range = Config::range_for_fruit(&fruit);
-            let price_range = Range{ start: range.start, end: range.end-1 };
-            let fruit_str = self.print_fruit(&fruit);
-            self.println(format!("\t{price_range_start}$ to {price_range_end}$ {fruit}",
-                    fruit = fruit_str,
-                    price_range_start = price_range.start,
-                    price_range_end = price_range.end));
+            let season = Config::season_for_fruit(&fruit);
+            let off_season = Season::iter().find(|other| *other != season).unwrap();
+            let range = Config::range_for_fruit(&fruit, &season);
+            let off_range = Config::range_for_fruit(&fruit, &off_season);
+            let line = self.locale.text("intro_range", &[
+                ("in_season", &self.price_range(&range)),
+                ("fruit", &self.print_fruit(&fruit)),
+                ("season", &self.locale.season(&season)),
+                ("off_season", &self.price_range(&off_range))]);
+            self.println(format!("\t{}", line))?;
+        }
+        self.println(String::from("      ***********************"))?;
+        self.println(String::from(""))?;
+        return Ok(());
+    }
+
+    /// Leaves the game screen and prints game over with the score being the amount of cash at the
+    /// end of the game. no points are given for any inventory fruits, only stone cold cash.
+    /// The seed is printed so the same market can be played again. Games played with the advisor's
+    /// help are marked as such
+    pub(crate) fn print_end(&mut self, player: &Player, seed: u64, assisted: bool) -> Result<(), GameError> {
+        self.ended = true;
+        terminal::leave_alternate_screen(&mut self.stdout)?;
+        let newline = self.locale.text("game_over", &[
+            ("color", &self.theme.fg(Role::Gain)),
+            ("score", &self.locale.money(player.get_cash())),
+            ("seed", &seed)]);
+        self.println(newline)?;
+        if assisted {
+            self.println(self.locale.text("assisted", &[]))?;
         }
-        self.println(String::from("      ***********************"));
-        self.println(String::from(""));
+        return Ok(());
+    }
+
+    /// Tells at game over that the optimal score is being searched for and any key skips the search
+    pub(crate) fn print_solving(&mut self) -> Result<(), GameError> {
+        self.println(self.locale.text("solving", &[]))?;
+        self.stdout.flush()?;
+        return Ok(());
+    }
+
+    /// Compares the score at game over to the optimal score of the market, see solver
+    pub(crate) fn print_optimal(&mut self, player: &Player, optimal: Money) -> Result<(), GameError> {
+        let percent = player.get_cash().get_cents() * 100 / optimal.get_cents().max(1);
+        let line = self.locale.text("optimal", &[("percent", &percent), ("optimal", &self.locale.money(optimal))]);
+        self.println(line)?;
+        self.stdout.flush()?;
+        return Ok(());
     }
-
-    /// Prints game over with the score being the amount of cash at the end of the game.
-    /// no points are given for any inventory fruits, only stone cold cash
-    pub(crate) fn print_end(&mut self, player: &Player) {
-        let newline = format!("Game over, Your score is: {bold}{green}{cash}${reset}",
-                              bold  = style::Bold,
-                              cash = &player.get_cash(),
-                              green = color::Fg(color::Green),
-                              reset = style::Reset);
-        self.println(newline);
-    }
-
-    /// Update first clears & moves the cursor up 5 lines. Then prints all 5 lines of information.
-    /// All lines are cleared & all lines are printed for each update.
-    fn update(&mut self){
-        if self.has_printed {
-            for _ in 0..5{
-                self.print(format!("{reset_cursor_left}{clear}{move_cursor_up}",
-                                   reset_cursor_left = termion::cursor::Left(100),
-                                   move_cursor_up = termion::cursor::Up(1),
-                                   clear = clear::CurrentLine));
+
+    /// Asks the player at game over whether to export the transaction ledger
+    pub(crate) fn print_export_prompt(&mut self) -> Result<(), GameError> {
+        let prompt = self.locale.text("export_prompt", &[("key", &self.key(&PlayerInteractions::Export).unwrap_or_default())]);
+        self.println(prompt)?;
+        self.stdout.flush()?;
+        return Ok(());
+    }
+
+    /// Place where the transaction ledger was exported to, or why it could not be, into the event
+    /// log. At game over the message is printed below the score instead
+    pub(crate) fn print_export(&mut self, path :&str, result :&Result<(), GameError>) -> Result<(), GameError> {
+        let message = match result{
+            Ok(()) => { self.locale.text("exported", &[("path", &path)]) }
+            Err(error) => { self.locale.text("export_failed", &[("path", &path), ("error", error)]) }
+        };
+        if self.ended {
+            self.println(message)?;
+            self.stdout.flush()?;
+            return Ok(());
+        }
+        return self.log(Verbosity::Terse, message);
+    }
+
+    /// Lay out the panels for the current terminal size and write the rows which changed since the
+    /// last update. The first update switches to the alternate screen, the intro stays on the main
+    /// screen to be seen again after the game.
+    fn update(&mut self) -> Result<(), GameError> {
+        if self.screen_reader.is_some() {
+            return Ok(());
+        }
+        terminal::enter_alternate_screen(&mut self.stdout)?;
+        let size = match termion::terminal_size() {
+            Ok((width, height)) if width > 0 && height > 0 => { (width, height) }
+            _ => { (80, 24) }
+        };
+        if size != self.size {
+            self.size = size;
+            self.drawn.clear();
+            write!(self.stdout, "{}", clear::All)?;
+        }
+
+        let screen = layout::compose(&self.sections(), size.0, size.1);
+        for (row, line) in screen.rows.iter().enumerate() {
+            if self.drawn.get(row) != Some(line) {
+                write!(self.stdout, "{}{}", cursor::Goto(1, row as u16 + 1), line)?;
             }
         }
-        self.println(String::from(&self.offers_left));
-        self.println(String::from(&self.offer_timeout));
-        self.println(String::from(&self.offer));
-        self.println(String::from(&self.status));
-        self.println(String::from(&self.player_feedback));
-        self.has_printed = true;
-        self.stdout.flush().unwrap();
+        self.drawn = screen.rows;
+        self.buttons = screen.buttons;
+        self.stdout.flush()?;
+        return Ok(());
     }
 
-    /// Create a new console session and put the terminal into raw mode
-    pub(crate) fn new()-> Output {
-        let stdout: RawTerminal<Stdout> = stdout().into_raw_mode().unwrap();
-        Output { stdout, has_printed: false, offers_left: "".to_string(), offer_timeout: "".to_string(), offer: "".to_string(), status: "".to_string(), player_feedback: "".to_string() }
+    /// Interaction of the button at the column and row of the screen as reported by the terminal,
+    /// counted from 1. None if there is no button
+    pub(crate) fn button_at(&self, column :u16, row :u16) -> Option<PlayerInteractions> {
+        let row = (row as usize).checked_sub(1)?;
+        let column = (column as usize).checked_sub(1)?;
+        let button = self.buttons.iter().find(|button| button.row == row && button.columns.contains(&column))?;
+        return PlayerInteractions::iter().nth(button.id);
     }
 
-    /// Will place debug string into player feedback line, before performing terminal update
-    pub(crate) fn _print_debugln(&mut self, str:String){
-        self.player_feedback = str;
-        self.update();
+    /// Returns button for the interaction covering the columns of the panel's line with index row
+    fn button(interaction :&PlayerInteractions, row :usize, columns :Range<usize>) -> Button {
+        let id = PlayerInteractions::iter().position(|other| other == *interaction).unwrap_or(usize::MAX);
+        return Button { row, columns, id };
     }
 
-    /// Place 'Skipping turn' into player feedback line, before performing terminal update
-    pub(crate) fn print_skipping_turn(&mut self){
-        self.player_feedback = String::from("Skipping turn");
-        self.update();
+    /// Panels of the game screen from top to bottom.
+    /// While paused the offer and its timeout are hidden so the player can not study the offer, as
+    /// are the offer's row in the price history and the charts which include its price.
+    /// Clicking the offer buys it, clicking the paused line resumes the game.
+    fn sections(&self) -> Vec<Section> {
+        let title = |name :&str| self.locale.text(name, &[]);
+        let (keys_line, keys_buttons) = self.keys_line();
+        let keys = Panel::fixed(Some(&title("panel_keys")), vec![keys_line]).with_buttons(keys_buttons);
+        if self.help {
+            return vec![Section::Full(Panel::text(&title("panel_help"), self.help_lines())), Section::Full(keys)];
+        }
+        let (offer, interaction) = if self.paused {
+            (vec![String::from(""),
+                  self.locale.text("paused", &[("key", &self.key(&PlayerInteractions::Pause).unwrap_or_default())]),
+                  String::from("")],
+             PlayerInteractions::Pause)
+        }else{
+            let mut offer = vec![self.offer_timeout.clone()];
+            offer.extend(self.offer.iter().cloned());
+            offer.extend(self.advice.iter().cloned());
+            (offer, PlayerInteractions::Buy)
+        };
+        let width = offer.get(1).map(|line| layout::width(line)).unwrap_or(0);
+        let offer = Panel::fixed(Some(&title("panel_offer")), offer)
+            .with_buttons(vec![Output::button(&interaction, 1, 0..width)]);
+        let offer = if self.show_charts && !self.paused {
+            Section::Split(offer, Panel::fixed(Some(&title("panel_charts")), self.charts.clone()))
+        }else{
+            Section::Full(offer)
+        };
+        //The current offer is the last row of the price history
+        let history = if self.paused {
+            self.history[..self.history.len().saturating_sub(1)].to_vec()
+        }else{
+            self.history.clone()
+        };
+        return vec![
+            Section::Full(Panel::fixed(None, vec![self.market.clone(), self.news.clone()])),
+            offer,
+            Section::Split(Panel::fixed(Some(&title("panel_inventory")), self.inventory.clone()),
+                           Panel::growing(&title("panel_history"), history)),
+            Section::Split(Panel::growing(&title("panel_events"), self.log.iter().cloned().collect()),
+                           Panel::growing(&title("panel_transactions"), self.transactions.clone())),
+            Section::Full(keys)
+        ];
     }
 
-    /// Place 'Not enough money' into player feedback line, before performing terminal update
-    pub(crate) fn print_no_offer(&mut self)
-    {
-        self.player_feedback = String::from("Not enough money");
-        self.update();
+    /// Returns lines of the help listing every key binding followed by the rules of the game
+    fn help_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("{bold}{keys}{reset}", bold = style::Bold, keys = self.locale.text("help_keys", &[]), reset = style::Reset)];
+        let label_width = PlayerInteractions::iter()
+            .map(|interaction| layout::width(self.locale.interaction(&interaction)))
+            .max()
+            .unwrap_or(0) + 2;
+        for interaction in PlayerInteractions::iter(){
+            let keys = self.key_map.get_keys(&interaction).iter()
+                .map(key_map::key_name)
+                .collect::<Vec<String>>()
+                .join(", ");
+            lines.push(format!("  {label}{keys}", label = layout::pad(self.locale.interaction(&interaction), label_width), keys = keys));
+        }
+        lines.push(String::new());
+        lines.push(format!("{bold}{rules}{reset}", bold = style::Bold, rules = self.locale.text("help_rules", &[]), reset = style::Reset));
+        let rules = [
+            self.locale.text("rule_score", &[("cash", &self.locale.money(Config::STARTING_CASH)), ("offers", &Config::AMOUNT_OF_OFFERS)]),
+            self.locale.text("rule_offers", &[]),
+            self.locale.text("rule_storage", &[
+                ("capacity", &Config::STORAGE_CAPACITY),
+                ("a", &Config::shelf_life_for_quality(&Quality::A)),
+                ("b", &Config::shelf_life_for_quality(&Quality::B)),
+                ("c", &Config::shelf_life_for_quality(&Quality::C))]),
+            self.locale.text("rule_selling", &[]),
+            self.locale.text("rule_seasons", &[("length", &Config::SEASON_LENGTH_OFFERS)]),
+            self.locale.text("rule_events", &[]),
+            self.locale.text("rule_auctions", &[]),
+            self.locale.text("rule_bulk", &[]),
+            self.locale.text("rule_info", &[]),
+            self.locale.text("rule_mouse", &[]),
+            self.locale.text("rule_advisor", &[])
+        ];
+        //Rules are wrapped to the terminal width, indented below the heading
+        let width = match self.screen_reader {
+            Some(_) => { usize::MAX }
+            None => { (self.size.0 as usize).saturating_sub(4).max(20) }
+        };
+        for rule in &rules {
+            lines.extend(layout::wrap(rule, width).iter().map(|line| format!("  {}", line)));
+        }
+        return lines;
     }
 
-    /// Place 'No such item in inventory' into player feedback line, before performing terminal update
-    pub(crate) fn print_no_such_in_inventory(&mut self) {
-        self.player_feedback = String::from("No such item in inventory");
-        self.update();
+    /// Redraw the screen, laying it out again if the terminal was resized. At game over the screen
+    /// is no longer drawn, the export prompt on the last line is written again instead
+    pub(crate) fn redraw(&mut self) -> Result<(), GameError> {
+        if self.ended {
+            if self.screen_reader.is_some() {
+                return Ok(());
+            }
+            write!(self.stdout, "{}\r{}", cursor::Up(1), clear::CurrentLine)?;
+            return self.print_export_prompt();
+        }
+        return self.update();
     }
 
-    /// Update current fruit offer and turns remaining lines, before performing terminal update
-    pub(crate) fn print_offer(&mut self, fruit :&Fruit, price :&u32, offers_left:&u32){
-        self.offers_left = format!("Offers left: {:0>2}", offers_left);
+    /// Create a new console session and put the terminal into raw mode, the key map is used for the
+    /// keys shown in help texts, the theme for all colors and the locale for all texts. The game is
+    /// announced for screen readers at the given verbosity instead of being drawn if screen_reader
+    /// is set
+    pub(crate) fn new(key_map :KeyMap, theme :Theme, locale :Locale, screen_reader :Option<Verbosity>)-> Result<Output, GameError> {
+        let stdout = stdout().into_raw_mode()?;
+        return Ok(Output::with_writer(Box::new(stdout), key_map, theme, locale, screen_reader));
+    }
 
-        self.offer =  format!("{fruit} for {price}$",
-                              fruit=self.print_fruit(&fruit),
-                              price=price);
+    /// Console session writing to stdout, which need not be a terminal, see new
+    fn with_writer(stdout :Box<dyn Write>, key_map :KeyMap, theme :Theme, locale :Locale, screen_reader :Option<Verbosity>)-> Output {
+        Output { stdout, key_map, theme, locale, size: (0, 0), drawn: Vec::new(), buttons: Vec::new(), market: "".to_string(), news: "".to_string(), offer_timeout: "".to_string(), offer: Vec::new(), inventory: Vec::new(), history: Vec::new(), charts: Vec::new(), show_charts: false, advice: None, log: VecDeque::new(), transactions: Vec::new(), paused: false, help: false, ended: false, screen_reader, time_announced: 100, transactions_announced: 0 }
+    }
 
-        self.reset_player_feedback();
+    /// Add message to the end of the event log and announce it at the verbosity, before performing
+    /// terminal update. Only the last Config::EVENT_LOG_LENGTH messages are kept
+    fn log(&mut self, verbosity :Verbosity, message :String) -> Result<(), GameError> {
+        self.announce(verbosity, &message)?;
+        self.log.push_back(message);
+        while self.log.len() > Config::EVENT_LOG_LENGTH {
+            self.log.pop_front();
+        }
+        return self.update();
+    }
+
+    /// Will place debug string into the event log, before performing terminal update
+    pub(crate) fn _print_debugln(&mut self, str:String) -> Result<(), GameError> {
+        return self.log(Verbosity::Verbose, str);
+    }
 
-        self.print_timeout(100);
+    /// Show or hide the help, before performing terminal update
+    pub(crate) fn print_help(&mut self, help :bool) -> Result<(), GameError> {
+        self.help = help;
+        if help {
+            for line in self.help_lines() {
+                self.announce(Verbosity::Terse, &line)?;
+            }
+        }else{
+            self.announce(Verbosity::Terse, &self.locale.text("help_closed", &[]))?;
+        }
+        return self.update();
     }
 
-    /// Set player feedback to default text which is a text showing player key options.
-    /// Does not perform terminal update
-    pub(crate) fn reset_player_feedback(&mut self){
-        self.player_feedback = format!("[{green}{bold}b{reset}]uy [{red}{bold}s{reset}]ell [{blue}{bold}n{reset}]ext offer [{blue}{bold}e{reset}]nd game",
-                                       bold = style::Bold,
-                                       red = color::Fg(color::Red),
-                                       green = color::Fg(color::Green),
-                                       blue = color::Fg(color::Blue),
-                                       reset = style::Reset);
+    /// Place prompt of the tutorial step into the event log, naming the key bound to the
+    /// interaction the prompt asks for and the price the store pays for the offer's fruit, before
+    /// performing terminal update
+    pub(crate) fn print_tutorial(&mut self, step :&str, interaction :&PlayerInteractions, offer :&FruitOffer) -> Result<(), GameError> {
+        let prompt = self.locale.tutorial(step, &[
+            ("key", &self.key(interaction).unwrap_or_default()),
+            ("price", &self.locale.money(offer.get_quality().sell_price(offer.get_resale_base_price())))]);
+        return self.log(Verbosity::Terse, self.locale.text("tutorial", &[("prompt", &prompt)]));
+    }
 
+    /// Hide or show the current offer depending on if the game is paused, before performing terminal update
+    pub(crate) fn print_paused(&mut self, paused :bool) -> Result<(), GameError> {
+        self.paused = paused;
+        let announcement = if paused {
+            self.locale.text("paused", &[("key", &self.key(&PlayerInteractions::Pause).unwrap_or_default())])
+        }else{
+            self.locale.text("resumed", &[])
+        };
+        self.announce(Verbosity::Terse, &announcement)?;
+        return self.update();
     }
 
-    /// Update line showing player's inventory of fruits, before performing terminal update
-    pub(crate) fn print_player(&mut self, player :&Player){
-        self.status = format!("{bold}{cash}{reset}$-{red}{bold}{apples}{reset}A-{yellow}{bold}{bananas}{reset}B-{brown}{bold}{coconuts}{reset}C-{magenta}{bold}{dragon_fruits}{reset}D-{green}{bold}{elder_berries}{reset}E",
-                              cash =player.get_cash(),
-                              bold = style::Bold,
-                              red = color::Fg(color::Red),
-                              yellow = color::Fg(color::Yellow),
-                              brown = color::Fg(color::Rgb(210,105,30)),
-                              magenta = color::Fg(color::Magenta),
-                              green = color::Fg(color::Green),
-                              apples = player.get_amount_of_fruit(Fruit::Apples),
-                              bananas = player.get_amount_of_fruit(Fruit::Banana),
-                              coconuts = player.get_amount_of_fruit(Fruit::Coconut),
-                              dragon_fruits = player.get_amount_of_fruit(Fruit::DragonFruit),
-                              elder_berries = player.get_amount_of_fruit(Fruit::Elderberry),
-                              reset = style::Reset);
-        self.update();
+    /// Place which bot plays the game into the event log
+    pub(crate) fn print_bot(&mut self, name :&str) -> Result<(), GameError> {
+        let message = self.locale.text("bot_playing", &[("bot", &name), ("key", &self.key(&PlayerInteractions::Pause).unwrap_or_default())]);
+        return self.log(Verbosity::Terse, message);
     }
 
-    /// Set fruit's price range info into player feedback line, before performing terminal update
-    pub(crate) fn print_info(&mut self, fruit :&Fruit, price_range :Range<u32>){
-        self.player_feedback = format!("{fruit} range is [{price_range_start} to {price_range_end}]",
-                                       fruit = self.print_fruit(&fruit),
-                                       price_range_start = price_range.start,
-                                       price_range_end = price_range.end);
+    /// Place 'Skipping turn' into the event log, before performing terminal update
+    pub(crate) fn print_skipping_turn(&mut self) -> Result<(), GameError> {
+        return self.log(Verbosity::Terse, self.locale.text("skipping_turn", &[]));
+    }
 
-        self.update();
+    /// Place feedback for an interaction which could not be performed into the event log, before
+    /// performing terminal update
+    pub(crate) fn print_error(&mut self, error :&GameError) -> Result<(), GameError> {
+        return self.log(Verbosity::Terse, self.error_text(error));
+    }
+
+    /// Returns what went wrong as told to the player
+    fn error_text(&self, error :&GameError) -> String {
+        return match error{
+            GameError::InsufficientFunds => { self.locale.text("error_insufficient_funds", &[]) }
+            GameError::NoInventory => { self.locale.text("error_no_inventory", &[]) }
+            GameError::OfferExpired => { self.locale.text("error_offer_expired", &[]) }
+            GameError::StorageFull => { self.locale.text("error_storage_full", &[("capacity", &Config::STORAGE_CAPACITY)]) }
+            GameError::AlreadyTopBidder => { self.locale.text("error_already_top_bidder", &[]) }
+            GameError::AuctionOnlyBids => { self.locale.text("error_auction_only_bids", &[]) }
+            GameError::CashOverflow => { self.locale.text("error_cash_overflow", &[]) }
+            GameError::AdvisorDisabled => { self.locale.text("error_advisor_disabled", &[]) }
+            GameError::InvalidArgument(arg) => { self.locale.text("error_invalid_argument", &[("argument", arg)]) }
+            GameError::InvalidConfig(message) => { self.locale.text("error_invalid_config", &[("message", message)]) }
+            GameError::Io(error) => { self.locale.text("error_io", &[("error", error)]) }
+        };
+    }
+
+    /// Update current fruit offer, season, market news and turns remaining lines and add the offer
+    /// to the price history, before performing terminal update
+    pub(crate) fn print_offer(&mut self, offer :&FruitOffer, offers_left:&u32) -> Result<(), GameError> {
+        let season = offer.get_season();
+        self.market = self.locale.text("market", &[
+            ("offers_left", &format!("{:0>2}", offers_left)),
+            ("season", &self.locale.season(&season)),
+            ("season_left", &Season::offers_left(offer.get_id()))]);
+
+        if Season::offers_left(offer.get_id()) == Config::SEASON_LENGTH_OFFERS.max(1) {
+            let announcement = self.locale.text("season_start", &[("season", &self.locale.season(&season)), ("length", &Config::SEASON_LENGTH_OFFERS)]);
+            self.announce(Verbosity::Normal, &announcement)?;
+        }
+
+        let news = match offer.get_event(){
+            None => {
+                self.locale.text("news_calm", &[])
+            }
+            Some(event) => {
+                self.locale.text("news_event", &[
+                    ("headline", &self.locale.event(event.get_name())),
+                    ("fruit", &self.print_fruit(&event.get_fruit())),
+                    ("offers", &event.get_offers_left())])
+            }
+        };
+        if news != self.news {
+            self.announce(Verbosity::Normal, &news)?;
+            self.news = news;
+        }
+        self.announce(Verbosity::Terse, &self.offer_announcement(offer, *offers_left))?;
+        let quality = offer.get_quality();
+        let buy_back = Quality::iter()
+            .map(|quality| format!("{} {}", quality.get_name(), self.locale.spoken_money(quality.sell_price(offer.get_resale_base_price()))))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let announcement = self.locale.text("announce_buy_back", &[("shelf_life", &Config::shelf_life_for_quality(&quality)), ("buy_back", &buy_back)]);
+        self.announce(Verbosity::Verbose, &announcement)?;
+        self.time_announced = 100;
+
+        self.offer = match offer.get_kind(){
+            OfferKind::Auction(lot) => {
+                self.auction_line(offer, lot.lot_size, None, offer.get_price())
+            }
+            _ => {
+                self.offer_line(offer, 0)
+            }
+        };
+
+        let history = self.history_line(offer);
+        self.history.push(history);
+
+        self.offer_timeout = String::new();
+        return self.print_timeout(100);
+    }
+
+    /// Returns announcement of a new offer for screen readers, naming the fruit, its grade and price
+    fn offer_announcement(&self, offer :&FruitOffer, offers_left :u32) -> String {
+        let grade = offer.get_quality().get_name();
+        let fruit = self.locale.fruit(&offer.get_fruit());
+        let price = self.locale.spoken_money(offer.get_price());
+        return match offer.get_kind(){
+            OfferKind::Fixed => {
+                self.locale.text("announce_fixed", &[("grade", &grade), ("fruit", &fruit), ("price", &price), ("offers_left", &offers_left)])
+            }
+            OfferKind::Tiered(tiers) => {
+                self.locale.text("announce_tiered", &[
+                    ("grade", &grade), ("fruit", &fruit), ("price", &price),
+                    ("discounted_price", &self.locale.spoken_money(tiers.discounted_price)),
+                    ("threshold", &tiers.threshold),
+                    ("offers_left", &offers_left)])
+            }
+            OfferKind::Bulk(lot) => {
+                self.locale.text("announce_lot", &[
+                    ("lot_size", &lot.lot_size), ("grade", &grade), ("fruit", &fruit), ("price", &price),
+                    ("unit_price", &self.locale.spoken_money_fraction(lot.unit_price(offer.get_price()), 2)),
+                    ("offers_left", &offers_left)])
+            }
+            OfferKind::Auction(lot) => {
+                self.locale.text("announce_auction", &[
+                    ("lot_size", &lot.lot_size), ("grade", &grade), ("fruit", &fruit), ("price", &price),
+                    ("offers_left", &offers_left)])
+            }
+        };
+    }
+
+    /// Update price charts with a row per fruit showing its most recent prices per fruit as bars,
+    /// followed by the lowest to highest price and the average price. Performs terminal update if
+    /// the charts are shown
+    pub(crate) fn print_price_history(&mut self, history :&PriceHistory) -> Result<(), GameError> {
+        //Prices are shown with one decimal, whole prices without
+        let decimals = |price :f64| if format!("{:.1}", price).ends_with(".0") { 0 } else { 1 };
+        let mut charts = Vec::new();
+        for fruit in Fruit::iter(){
+            let fruit_str = match self.screen_reader {
+                Some(_) => { format!("{} ", self.locale.fruit(&fruit)) }
+                None => { layout::fit(&self.print_fruit(&fruit), self.fruit_width() + 1) }
+            };
+            let prices = history.get_prices(fruit);
+            let recent = &prices[prices.len().saturating_sub(Config::SPARKLINE_LENGTH)..];
+            charts.push(match history.get_stats(fruit){
+                None => {
+                    format!("{}{}", fruit_str, self.locale.text("chart_not_offered", &[]))
+                }
+                Some(stats) if self.screen_reader.is_some() => {
+                    let price = |price :f64| self.locale.spoken_money_fraction(price, decimals(price));
+                    self.locale.text("announce_chart", &[
+                        ("fruit", &self.locale.fruit(&fruit)),
+                        ("low", &price(stats.min)),
+                        ("high", &price(stats.max)),
+                        ("average", &price(stats.average))])
+                }
+                Some(stats) => {
+                    let price = |price :f64| self.locale.money_fraction(price, decimals(price));
+                    format!("{fruit}{chart:<width$} {stats}",
+                            fruit = fruit_str,
+                            chart = layout::sparkline(recent),
+                            width = Config::SPARKLINE_LENGTH,
+                            stats = self.locale.text("chart", &[
+                                ("low", &price(stats.min)),
+                                ("high", &price(stats.max)),
+                                ("average", &price(stats.average))]))
+                }
+            });
+        }
+        self.charts = charts;
+        if self.show_charts {
+            return self.update();
+        }
+        return Ok(());
+    }
+
+    /// Update the advisor's rating of the offer below the offer, None hides it. Screen readers are
+    /// read the rating when it changes. Performs terminal update if the rating changed
+    pub(crate) fn print_advice(&mut self, advice :Option<&Advice>) -> Result<(), GameError> {
+        let text = |advice :&Advice, value :String| {
+            let message = if advice.rating == Rating::SellNow { "advice_sell" } else { "advice_buy" };
+            let rating = self.locale.text(&format!("rating_{}", advice.rating.get_name()), &[]);
+            self.locale.text(message, &[("rating", &rating), ("value", &value)])
+        };
+        let line = advice.map(|advice| text(advice, self.locale.money_fraction(advice.expected_value, 2)));
+        if line == self.advice {
+            return Ok(());
+        }
+        let announcement = match advice {
+            Some(advice) => { text(advice, self.locale.spoken_money_fraction(advice.expected_value, 2)) }
+            None => { self.locale.text("advisor_hidden", &[]) }
+        };
+        self.announce(Verbosity::Terse, &announcement)?;
+        self.advice = line;
+        return self.update();
+    }
+
+    /// Show or hide the price charts, before performing terminal update. Screen readers are read
+    /// the charts' prices when they are shown
+    pub(crate) fn toggle_charts(&mut self) -> Result<(), GameError> {
+        self.show_charts = !self.show_charts;
+        if self.show_charts {
+            for line in self.charts.clone() {
+                self.announce(Verbosity::Terse, &line)?;
+            }
+        }else{
+            self.announce(Verbosity::Terse, &self.locale.text("charts_hidden", &[]))?;
+        }
+        return self.update();
+    }
+
+    /// Returns price history line for the offer, the offer number followed by fruit, grade and price
+    fn history_line(&self, offer :&FruitOffer) -> String {
+        let price = self.locale.money(offer.get_price());
+        let price = match offer.get_kind(){
+            OfferKind::Fixed => { price }
+            OfferKind::Auction(lot) => { self.locale.text("history_auction", &[("price", &price), ("lot_size", &lot.lot_size)]) }
+            OfferKind::Bulk(lot) => { self.locale.text("history_lot", &[("price", &price), ("lot_size", &lot.lot_size)]) }
+            OfferKind::Tiered(tiers) => {
+                self.locale.text("history_tiered", &[
+                    ("price", &price),
+                    ("discounted_price", &self.locale.money(tiers.discounted_price)),
+                    ("threshold", &tiers.threshold)])
+            }
+        };
+        return format!("{id:>3} {fruit} {quality} {price}",
+                       id = offer.get_id() + 1,
+                       fruit = layout::fit(&self.print_fruit(&offer.get_fruit()), self.fruit_width()),
+                       quality = offer.get_quality().get_name(),
+                       price = price);
+    }
+
+    /// Returns offer lines for fixed price, bulk lot and volume discounted offers, the second line
+    /// showing the buy back prices of each grade. Bulk lots show the price of one fruit for
+    /// comparison and volume discounts show how many fruits are left to buy until the discount applies
+    fn offer_line(&self, offer :&FruitOffer, units_bought :u32) -> Vec<String> {
+        let quality = offer.get_quality();
+        let price = match offer.get_kind(){
+            OfferKind::Bulk(lot) => {
+                self.locale.text("offer_lot_price", &[
+                    ("price", &self.locale.money(offer.get_price())),
+                    ("unit_price", &self.locale.money_fraction(lot.unit_price(offer.get_price()), 2))])
+            }
+            OfferKind::Tiered(tiers) if units_bought < tiers.threshold => {
+                self.locale.text("offer_tiered_price", &[
+                    ("price", &self.locale.money(offer.get_price())),
+                    ("discounted_price", &self.locale.money(tiers.discounted_price)),
+                    ("threshold", &tiers.threshold),
+                    ("left", &(tiers.threshold - units_bought))])
+            }
+            OfferKind::Tiered(tiers) => {
+                self.locale.text("offer_discounted_price", &[
+                    ("color", &self.theme.fg(Role::Gain)),
+                    ("discounted_price", &self.locale.money(tiers.discounted_price))])
+            }
+            _ => {
+                self.locale.money(offer.get_price())
+            }
+        };
+        let fruit = self.print_fruit(&offer.get_fruit());
+        let line = match offer.get_kind(){
+            OfferKind::Bulk(lot) => {
+                self.locale.text("offer_lot", &[("lot_size", &lot.lot_size), ("grade", &quality.get_name()), ("fruit", &fruit), ("price", &price)])
+            }
+            _ => {
+                self.locale.text("offer_fixed", &[("grade", &quality.get_name()), ("fruit", &fruit), ("price", &price)])
+            }
+        };
+        let buy_back = Quality::iter()
+            .map(|quality| format!("{}:{}", quality.get_name(), self.locale.money(quality.sell_price(offer.get_resale_base_price()))))
+            .collect::<Vec<String>>()
+            .join(" ");
+        return vec![
+            line,
+            self.locale.text("offer_buy_back", &[("shelf_life", &Config::shelf_life_for_quality(&quality)), ("buy_back", &buy_back)])
+        ];
+    }
+
+    /// Update offer line after fruit has been bought from the offer, before performing terminal update
+    pub(crate) fn print_units_bought(&mut self, offer :&FruitOffer, units_bought :u32) -> Result<(), GameError> {
+        self.offer = self.offer_line(offer, units_bought);
+        return self.update();
+    }
+
+    /// Returns offer lines for an auction showing the lot and the next bid, the second line showing
+    /// who holds the top bid
+    fn auction_line(&self, offer :&FruitOffer, lot_size :u32, top_bid :Option<(Bidder, Money)>, next_bid :Money) -> Vec<String> {
+        let quality = offer.get_quality();
+        let top_bid = match top_bid{
+            None => {
+                self.locale.text("auction_no_bids", &[])
+            }
+            Some((Bidder::Player, bid)) => {
+                self.locale.text("auction_player_top", &[("color", &self.theme.fg(Role::Gain)), ("bid", &self.locale.money(bid))])
+            }
+            Some((Bidder::Rival, bid)) => {
+                self.locale.text("auction_rival_top", &[("color", &self.theme.fg(Role::Loss)), ("bid", &self.locale.money(bid))])
+            }
+        };
+        return vec![
+            self.locale.text("auction", &[
+                ("lot_size", &lot_size),
+                ("grade", &quality.get_name()),
+                ("fruit", &self.print_fruit(&offer.get_fruit())),
+                ("next_bid", &self.locale.money(next_bid))]),
+            self.locale.text("auction_bids", &[("top_bid", &top_bid), ("shelf_life", &Config::shelf_life_for_quality(&quality))])
+        ];
+    }
+
+    /// Update offer line with the auction's current bids, before performing terminal update
+    pub(crate) fn print_auction(&mut self, offer :&FruitOffer, auction :&Auction) -> Result<(), GameError> {
+        let next_bid = self.locale.spoken_money(auction.get_next_bid());
+        let announcement = match auction.get_top_bid(){
+            None => { self.locale.text("announce_no_bids", &[("next_bid", &next_bid)]) }
+            Some((Bidder::Player, bid)) => { self.locale.text("announce_player_top", &[("bid", &self.locale.spoken_money(bid)), ("next_bid", &next_bid)]) }
+            Some((Bidder::Rival, bid)) => { self.locale.text("announce_rival_top", &[("bid", &self.locale.spoken_money(bid)), ("next_bid", &next_bid)]) }
+        };
+        self.announce(Verbosity::Terse, &announcement)?;
+        self.offer = self.auction_line(offer, auction.get_lot_size(), auction.get_top_bid(), auction.get_next_bid());
+        return self.update();
+    }
+
+    /// Place outcome of an ended auction into the event log, before performing terminal update.
+    /// won is false when the rival won, an error means the player won but could not take the lot
+    pub(crate) fn print_auction_result(&mut self, fruit :&Fruit, auction :&Auction, won :&Result<bool, GameError>) -> Result<(), GameError> {
+        let fruit = self.print_fruit(fruit);
+        let lot_size = auction.get_lot_size();
+        let message = match (auction.get_top_bid(), won){
+            (Some((_, bid)), Ok(true)) => {
+                self.locale.text("auction_won", &[
+                    ("color", &self.theme.fg(Role::Gain)),
+                    ("lot_size", &lot_size),
+                    ("fruit", &fruit),
+                    ("bid", &self.locale.money(bid))])
+            }
+            (Some((_, bid)), Err(error)) => {
+                self.locale.text("auction_won_failed", &[
+                    ("color", &self.theme.fg(Role::Loss)),
+                    ("lot_size", &lot_size),
+                    ("fruit", &fruit),
+                    ("bid", &self.locale.money(bid)),
+                    ("error", &self.error_text(error))])
+            }
+            (Some((_, bid)), Ok(false)) => {
+                self.locale.text("auction_lost", &[
+                    ("color", &self.theme.fg(Role::Loss)),
+                    ("lot_size", &lot_size),
+                    ("fruit", &fruit),
+                    ("bid", &self.locale.money(bid))])
+            }
+            (None, _) => {
+                self.locale.text("auction_ended_without_bids", &[])
+            }
+        };
+        return self.log(Verbosity::Terse, message);
+    }
+
+    /// Returns line showing player key options for the keys panel, generated from the key map,
+    /// together with a button for each key option
+    fn keys_line(&self) -> (String, Vec<Button>) {
+        let mut line = String::new();
+        let mut buttons = Vec::new();
+        for interaction in PlayerInteractions::iter() {
+            let key = match self.key(&interaction) {
+                Some(key) => { key }
+                None => { continue; }
+            };
+            if !line.is_empty() {
+                line.push_str("  ");
+            }
+            let option = format!("{} {}", key, self.locale.interaction(&interaction));
+            let start = layout::width(&line);
+            buttons.push(Output::button(&interaction, 0, start..start + layout::width(&option)));
+            line.push_str(&option);
+        }
+        return (line, buttons);
+    }
+
+    /// Returns the first key bound to the interaction in brackets, colored by what the interaction
+    /// does. None if no key is bound to the interaction
+    fn key(&self, interaction :&PlayerInteractions) -> Option<String> {
+        let name = self.key_map.get_key_name(interaction)?;
+        let color = match interaction{
+            PlayerInteractions::Buy => { self.theme.fg(Role::Gain) }
+            PlayerInteractions::Sell => { self.theme.fg(Role::Loss) }
+            _ => { self.theme.fg(Role::Key) }
+        };
+        return Some(format!("[{color}{bold}{name}{reset}]",
+                            color = color,
+                            bold = style::Bold,
+                            name = name,
+                            reset = style::Reset));
+    }
+
+    /// Update inventory table showing player's cash, storage and fruits per grade, before performing
+    /// terminal update
+    pub(crate) fn print_player(&mut self, player :&Player) -> Result<(), GameError> {
+        let mut inventory = vec![
+            self.locale.text("inventory_cash", &[
+                ("cash", &self.locale.money(player.get_cash())),
+                ("used", &player.get_amount_of_fruits()),
+                ("capacity", &Config::STORAGE_CAPACITY)]),
+            format!("{bold}{fruit}{grades}{total:>7}{reset}",
+                    bold = style::Bold,
+                    fruit = layout::pad(&self.locale.text("inventory_fruit", &[]), self.fruit_width()),
+                    grades = Quality::iter().map(|quality| format!("{:>4}", quality.get_name())).collect::<String>(),
+                    total = self.locale.text("inventory_total", &[]),
+                    reset = style::Reset)
+        ];
+        for fruit in Fruit::iter(){
+            let amounts: String = Quality::iter()
+                .map(|quality| format!("{:>4}", player.get_amount_of_fruit_quality(fruit, quality)))
+                .collect();
+            inventory.push(format!("{fruit}{amounts}{total:>7}",
+                                   fruit = layout::fit(&self.print_fruit(&fruit), self.fruit_width()),
+                                   amounts = amounts,
+                                   total = player.get_amount_of_fruit(fruit)));
+        }
+        self.inventory = inventory;
+
+        let ledger = player.get_ledger();
+        for transaction in &ledger[self.transactions_announced.min(ledger.len())..] {
+            let name = match transaction.side{
+                Side::Buy => { "announce_bought" }
+                Side::Sell => { "announce_sold" }
+            };
+            let announcement = self.locale.text(name, &[
+                ("quantity", &transaction.quantity),
+                ("fruit", &self.locale.fruit(&transaction.fruit)),
+                ("price", &self.locale.spoken_money(transaction.price)),
+                ("cash", &self.locale.spoken_money(transaction.cash))]);
+            self.announce(Verbosity::Terse, &announcement)?;
+            let announcement = self.locale.text("announce_storage", &[("used", &player.get_amount_of_fruits()), ("capacity", &Config::STORAGE_CAPACITY)]);
+            self.announce(Verbosity::Verbose, &announcement)?;
+        }
+        self.transactions_announced = ledger.len();
+        self.transactions = ledger[ledger.len().saturating_sub(Config::EVENT_LOG_LENGTH)..].iter()
+            .map(|transaction| self.transaction_line(transaction))
+            .collect();
+        return self.update();
+    }
+
+    /// Returns transaction log line, the offer number followed by what was bought or sold for how
+    /// much and the cash left
+    fn transaction_line(&self, transaction :&Transaction) -> String {
+        let buy = self.locale.text("side_buy", &[]);
+        let sell = self.locale.text("side_sell", &[]);
+        let width = layout::width(&buy).max(layout::width(&sell));
+        let side = match transaction.side{
+            Side::Buy => { format!("{gain}{side}{reset}", gain = self.theme.fg(Role::Gain), side = layout::pad(&buy, width), reset = style::Reset) }
+            Side::Sell => { format!("{loss}{side}{reset}", loss = self.theme.fg(Role::Loss), side = layout::pad(&sell, width), reset = style::Reset) }
+        };
+        return format!("{offer:>3} {side} {quantity:>2} {fruit} {price:>5}  {cash}",
+                       offer = transaction.offer_id + 1,
+                       side = side,
+                       quantity = transaction.quantity,
+                       fruit = layout::fit(&self.print_fruit(&transaction.fruit), self.fruit_width()),
+                       price = self.locale.money(transaction.price),
+                       cash = self.locale.text("transaction_cash", &[("cash", &self.locale.money(transaction.cash))]));
+    }
+
+    /// Place which fruits spoiled in the player's inventory into the event log, before performing
+    /// terminal update
+    pub(crate) fn print_spoiled(&mut self, spoiled :&[(Fruit,u32)]) -> Result<(), GameError> {
+        let spoiled: Vec<String> = spoiled.iter()
+            .map(|(fruit, amount)| format!("{} {}", amount, self.print_fruit(fruit)))
+            .collect();
+        return self.log(Verbosity::Normal, self.locale.text("spoiled", &[("fruits", &spoiled.join(", "))]));
+    }
+
+    /// Place fruit's price range info into the event log, before performing terminal update
+    pub(crate) fn print_info(&mut self, fruit :&Fruit, price_range :RangeInclusive<Money>) -> Result<(), GameError> {
+        let message = self.locale.text("info", &[
+            ("fruit", &self.print_fruit(fruit)),
+            ("range", &self.price_range(&price_range))]);
+
+        return self.log(Verbosity::Terse, message);
+    }
+
+    /// Returns lowest to highest price, both included
+    fn price_range(&self, range :&RangeInclusive<Money>) -> String {
+        return self.locale.text("price_range", &[("low", &self.locale.money(*range.start())), ("high", &self.locale.money(*range.end()))]);
     }
 
     /// Update offer timeout line which is a line consisting of max 20 '_' above the current offer
-    /// the amount of '_' is between 0 and 20. Color of line starts green but becomes red when
-    /// little time is left. Performs terminal update if there is need for it.
-    pub(crate) fn print_timeout(&mut self, proc:u32){
+    /// the amount of '_' is between 0 and 20. Color of line starts as the theme's gain color but
+    /// becomes its loss color when little time is left. Performs terminal update if there is need for it.
+    pub(crate) fn print_timeout(&mut self, proc:u32) -> Result<(), GameError> {
+        if let Some(announced) = self.screen_reader {
+            let reached = (proc..self.time_announced)
+                .filter(|percent| Verbosity::for_time_remaining(*percent).is_some_and(|verbosity| verbosity <= announced))
+                .min();
+            self.time_announced = self.time_announced.min(proc);
+            if let Some(percent) = reached {
+                self.announce(Verbosity::Terse, &self.locale.text("announce_time", &[("percent", &percent)]))?;
+            }
+        }
         let mut amount = proc / 5;
 
         if amount > 20 { amount = 20 };
 
         let mut progress;
         if amount > 13 {
-            progress = format!("{green}",
-                      green = color::Fg(color::Green));
+            progress = self.theme.fg(Role::Gain);
         }else if amount > 7 {
-            progress = format!("{yellow}",
-                               yellow = color::Fg(color::Yellow));
+            progress = self.theme.fg(Role::Warning);
         }else {
-            progress = format!("{red}",
-                               red = color::Fg(color::Red));
+            progress = self.theme.fg(Role::Loss);
         }
 
         for _ in 1..amount+1{
@@ -189,49 +865,83 @@ impl Output {
 
         if self.offer_timeout != progress {
             self.offer_timeout = progress;
-            self.update();
+            return self.update();
         }
+        return Ok(());
     }
 
-    /// Returns formatted string for fruit name
-    fn print_fruit(&mut self, fruit :&Fruit)-> String{
-        match fruit{
-            Fruit::Apples => {
-                return format!("{red}Apple{reset}",
-                                   red = color::Fg(color::Red),
-                                   reset = style::Reset);
-            }
-            Fruit::Banana => {
-                return format!("{yellow}Banana{reset}",
-                                   yellow = color::Fg(color::Yellow),
-                                   reset = style::Reset);
-            }
-            Fruit::Coconut => {
-                return format!("{brown}Coconut{reset}",
-                                   brown = color::Fg(color::Rgb(210,105,30)),
-                                   reset = style::Reset);
-            }
-            Fruit::DragonFruit => {
-                return format!("{magenta}DragonFruit{reset}",
-                                   magenta = color::Fg(color::Magenta),
-                                   reset = style::Reset);
-            }
-            Fruit::Elderberry => {
-                return format!("{green}Elderberry{reset}",
-                                   green = color::Fg(color::Green),
-                                   reset = style::Reset);
-            }
+    /// Returns formatted string for fruit name, colored by the theme
+    fn print_fruit(&self, fruit :&Fruit)-> String{
+        return format!("{color}{name}{reset}",
+                       name = self.locale.fruit(fruit),
+                       color = self.theme.fg(Role::Fruit(*fruit)),
+                       reset = style::Reset);
+    }
+
+    /// Width of the fruit column in tables, fitting the longest fruit name with a space after it
+    fn fruit_width(&self) -> usize {
+        return Fruit::iter().map(|fruit| layout::width(self.locale.fruit(&fruit))).max().unwrap_or(0) + 1;
+    }
+
+    /// Writes to terminal with '\n\r' ending, without styles in screen reader mode
+    fn println(&mut self, str: String) -> Result<(), GameError> {
+        let str = match self.screen_reader {
+            Some(_) => { layout::plain(&str) }
+            None => { str }
+        };
+        write!(self.stdout,"{}\n\r", str)?;
+        return Ok(());
+    }
+
+    /// Writes announcement for screen readers as a line of plain text, if in screen reader mode and
+    /// the verbosity is announced
+    fn announce(&mut self, verbosity :Verbosity, message :&str) -> Result<(), GameError> {
+        if self.screen_reader.is_some_and(|announced| verbosity <= announced) {
+            self.println(String::from(message))?;
+            self.stdout.flush()?;
         }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use super::*;
+    use crate::console::theme::{ColorDepth, ThemeName};
+
+    fn output() -> Output {
+        let theme = Theme::new(ThemeName::Monochrome, ColorDepth::NoColor);
+        return Output::with_writer(Box::new(io::sink()), KeyMap::new(), theme, Locale::english(), None);
+    }
+
+    /// Screen of the game with an offer, its price history and charts shown, paused or not. The
+    /// offer's price is 7.77, shown as 7.8 in the charts
+    fn screen(paused :bool) -> String {
+        let mut output = output();
+        let mut history = PriceHistory::new();
+        let price = Money::from_cents(777);
+        let offer = FruitOffer::scripted(0, Fruit::Coconut, Quality::B, price, price, OfferKind::Fixed);
+        history.record(&offer);
+        output.print_offer(&offer, &99).unwrap();
+        output.print_player(&Player::new(Config::STARTING_CASH)).unwrap();
+        output.print_price_history(&history).unwrap();
+        output.toggle_charts().unwrap();
+        output.print_paused(paused).unwrap();
+        return layout::compose(&output.sections(), 120, 40).rows.join("\n");
     }
 
-    /// Writes to terminal with '\n\r' ending
-    fn println(&mut self, str: String) {
-        write!(self.stdout,"{}\n\r", str).unwrap();
+    #[test]
+    fn offer_price_is_shown_while_playing() {
+        let screen = screen(false);
+        assert_eq!(screen.matches("7.77").count(), 2, "{}", screen);
+        assert!(screen.contains("7.8"), "{}", screen);
     }
 
-    /// Writes to terminal
-    fn print(&mut self, str: String)
-    {
-        write!(self.stdout,"{}", str).unwrap();
+    #[test]
+    fn offer_price_is_hidden_while_paused() {
+        let screen = screen(true);
+        assert!(!screen.contains("7.77"), "{}", screen);
+        assert!(!screen.contains("7.8"), "{}", screen);
     }
-}
\ No newline at end of file
+}
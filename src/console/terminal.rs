@@ -0,0 +1,79 @@
+use std::io::{self, stdout, Write};
+use std::mem::MaybeUninit;
+use std::panic;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Terminal settings from before the game put the terminal into raw mode
+static ORIGINAL_TERMIOS: Mutex<Option<libc::termios>> = Mutex::new(None);
+
+/// Turns on reporting of mouse presses and releases, in the SGR format and in the older formats for
+/// terminals without it
+const ENTER_MOUSE: &str = "\x1b[?1000h\x1b[?1015h\x1b[?1006h";
+
+/// Turns off reporting of the mouse
+const EXIT_MOUSE: &str = "\x1b[?1006l\x1b[?1015l\x1b[?1000l";
+
+/// Whether the game is drawn on the alternate screen, the main screen is only switched back to if
+/// it was left since switching back also restores the cursor to where it was when it was left
+static ALTERNATE_SCREEN: AtomicBool = AtomicBool::new(false);
+
+/// Guard which restores the terminal to the settings it had when the guard was created. The
+/// terminal is restored when the guard is dropped and when the game panics, the panic hook restores
+/// the terminal before the panic message is printed so the message is readable.
+/// Create the guard before anything puts the terminal into raw mode.
+pub(crate) struct TerminalGuard {}
+
+impl TerminalGuard {
+    pub(crate) fn new() -> TerminalGuard {
+        let mut termios = MaybeUninit::<libc::termios>::uninit();
+        if unsafe { libc::tcgetattr(libc::STDOUT_FILENO, termios.as_mut_ptr()) } == 0 {
+            *ORIGINAL_TERMIOS.lock().unwrap_or_else(|e| e.into_inner()) = Some(unsafe { termios.assume_init() });
+        }
+
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            restore_terminal();
+            println!();
+            default_hook(info);
+        }));
+        return TerminalGuard {};
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+/// Switch to the alternate screen with a hidden cursor, the main screen's contents are kept and
+/// shown again when leaving the alternate screen. The mouse is reported while on the alternate
+/// screen so the game's buttons can be clicked
+pub(crate) fn enter_alternate_screen(out :&mut impl Write) -> io::Result<()> {
+    if !ALTERNATE_SCREEN.swap(true, Ordering::SeqCst) {
+        write!(out, "{}{}{}{}", termion::screen::ToAlternateScreen, termion::cursor::Hide, termion::clear::All, ENTER_MOUSE)?;
+    }
+    return Ok(());
+}
+
+/// Switch back to the main screen, stop reporting the mouse and show the cursor
+pub(crate) fn leave_alternate_screen(out :&mut impl Write) -> io::Result<()> {
+    if ALTERNATE_SCREEN.swap(false, Ordering::SeqCst) {
+        write!(out, "{}{}{}{}", EXIT_MOUSE, termion::style::Reset, termion::screen::ToMainScreen, termion::cursor::Show)?;
+    }
+    return Ok(());
+}
+
+/// Restore the terminal settings saved by TerminalGuard, leave the alternate screen, reset colors
+/// and show the cursor
+pub(crate) fn restore_terminal() {
+    let original = *ORIGINAL_TERMIOS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(termios) = original {
+        unsafe { libc::tcsetattr(libc::STDOUT_FILENO, libc::TCSANOW, &termios) };
+    }
+    let mut stdout = stdout();
+    let _ = leave_alternate_screen(&mut stdout);
+    let _ = write!(stdout, "{}{}", termion::style::Reset, termion::cursor::Show);
+    let _ = stdout.flush();
+}
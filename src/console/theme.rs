@@ -0,0 +1,259 @@
+use std::env;
+
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+use termion::color;
+
+use crate::config::file::ConfigFile;
+use crate::fruit::Fruit;
+use crate::game::error::GameError;
+
+/// Environment variable which turns colors off when set to anything, see https://no-color.org
+const NO_COLOR_ENV: &str = "NO_COLOR";
+
+/// What a color is used for, each theme picks its own shade for each role
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Role {
+    /// Buying, winning and plenty of time left
+    Gain,
+    /// Selling, losing and running out of time
+    Loss,
+    /// Time running short
+    Warning,
+    /// Keys of interactions which neither buy nor sell
+    Key,
+    /// Name of a fruit
+    Fruit(Fruit),
+    /// Letters of the game's title, the index cycles through the theme's title colors
+    Title(usize)
+}
+
+/// Color of a role. Basic colors are one of the 16 terminal colors, 0 to 7 and their bright variants
+/// 8 to 15, and look the same at every color depth. RGB colors are shown as given on true color
+/// terminals, as the closest of the 256 palette colors on 256 color terminals, and as their basic
+/// fallback color on terminals with 16 colors
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Shade {
+    Basic(u8),
+    Rgb(u8, u8, u8, u8)
+}
+
+/// How many colors the terminal can show
+#[derive(Clone, Copy, Debug, EnumIter, Eq, PartialEq)]
+pub(crate) enum ColorDepth {
+    NoColor,
+    Ansi16,
+    Ansi256,
+    TrueColor
+}
+
+impl ColorDepth {
+    /// Name used for the color depth in the config file
+    pub(crate) fn get_name(&self) -> &'static str {
+        match self{
+            ColorDepth::NoColor => {"none"}
+            ColorDepth::Ansi16 => {"16"}
+            ColorDepth::Ansi256 => {"256"}
+            ColorDepth::TrueColor => {"truecolor"}
+        }
+    }
+
+    /// Color depth of the terminal the game runs in. No colors are used when NO_COLOR is set or the
+    /// terminal is dumb, true color when COLORTERM says so and 256 colors when TERM says so. Other
+    /// terminals are expected to show the 16 basic colors
+    fn detect() -> ColorDepth {
+        if env::var_os(NO_COLOR_ENV).is_some_and(|value| !value.is_empty()) {
+            return ColorDepth::NoColor;
+        }
+        let colorterm = env::var("COLORTERM").unwrap_or_default();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return ColorDepth::TrueColor;
+        }
+        let term = env::var("TERM").unwrap_or_default();
+        if term.is_empty() || term == "dumb" {
+            return ColorDepth::NoColor;
+        }
+        if term.contains("256color") {
+            return ColorDepth::Ansi256;
+        }
+        return ColorDepth::Ansi16;
+    }
+}
+
+/// Set of colors the game is drawn with
+#[derive(Clone, Copy, Debug, EnumIter, Eq, PartialEq)]
+pub(crate) enum ThemeName {
+    Default,
+    /// Bright colors only, for dim screens and low vision
+    HighContrast,
+    /// Okabe-Ito palette, which keeps gains and losses apart for red-green color blindness
+    Colorblind,
+    /// No colors, only bold text
+    Monochrome
+}
+
+impl ThemeName {
+    /// Name used for the theme in the config file
+    pub(crate) fn get_name(&self) -> &'static str {
+        match self{
+            ThemeName::Default => {"default"}
+            ThemeName::HighContrast => {"high-contrast"}
+            ThemeName::Colorblind => {"colorblind"}
+            ThemeName::Monochrome => {"monochrome"}
+        }
+    }
+
+    /// Shade of the role in the theme, None if the theme leaves it uncolored
+    fn shade(&self, role :Role) -> Option<Shade> {
+        let shade = match self{
+            ThemeName::Default => {
+                match role{
+                    Role::Gain => { Shade::Basic(2) }
+                    Role::Loss => { Shade::Basic(1) }
+                    Role::Warning => { Shade::Basic(3) }
+                    Role::Key => { Shade::Basic(4) }
+                    Role::Fruit(Fruit::Apples) => { Shade::Basic(1) }
+                    Role::Fruit(Fruit::Banana) => { Shade::Basic(3) }
+                    Role::Fruit(Fruit::Coconut) => { Shade::Rgb(210, 105, 30, 3) }
+                    Role::Fruit(Fruit::DragonFruit) => { Shade::Basic(5) }
+                    Role::Fruit(Fruit::Elderberry) => { Shade::Basic(2) }
+                    Role::Title(index) => {
+                        [Shade::Basic(1), Shade::Rgb(255, 165, 0, 3), Shade::Basic(3), Shade::Basic(2),
+                         Shade::Rgb(0, 255, 255, 6), Shade::Basic(4), Shade::Rgb(128, 0, 128, 5)][index % 7]
+                    }
+                }
+            }
+            ThemeName::HighContrast => {
+                match role{
+                    Role::Gain => { Shade::Basic(10) }
+                    Role::Loss => { Shade::Basic(9) }
+                    Role::Warning => { Shade::Basic(11) }
+                    Role::Key => { Shade::Basic(14) }
+                    Role::Fruit(Fruit::Apples) => { Shade::Basic(9) }
+                    Role::Fruit(Fruit::Banana) => { Shade::Basic(11) }
+                    Role::Fruit(Fruit::Coconut) => { Shade::Basic(15) }
+                    Role::Fruit(Fruit::DragonFruit) => { Shade::Basic(13) }
+                    Role::Fruit(Fruit::Elderberry) => { Shade::Basic(10) }
+                    Role::Title(index) => {
+                        [Shade::Basic(9), Shade::Basic(11), Shade::Basic(10), Shade::Basic(14),
+                         Shade::Basic(12), Shade::Basic(13)][index % 6]
+                    }
+                }
+            }
+            ThemeName::Colorblind => {
+                let orange = Shade::Rgb(230, 159, 0, 3);
+                let sky_blue = Shade::Rgb(86, 180, 233, 14);
+                let bluish_green = Shade::Rgb(0, 158, 115, 6);
+                let yellow = Shade::Rgb(240, 228, 66, 11);
+                let blue = Shade::Rgb(0, 114, 178, 12);
+                let vermillion = Shade::Rgb(213, 94, 0, 1);
+                let reddish_purple = Shade::Rgb(204, 121, 167, 13);
+                match role{
+                    Role::Gain => { blue }
+                    Role::Loss => { vermillion }
+                    Role::Warning => { yellow }
+                    Role::Key => { sky_blue }
+                    Role::Fruit(Fruit::Apples) => { vermillion }
+                    Role::Fruit(Fruit::Banana) => { yellow }
+                    Role::Fruit(Fruit::Coconut) => { orange }
+                    Role::Fruit(Fruit::DragonFruit) => { reddish_purple }
+                    Role::Fruit(Fruit::Elderberry) => { bluish_green }
+                    Role::Title(index) => {
+                        [vermillion, orange, yellow, bluish_green, sky_blue, blue, reddish_purple][index % 7]
+                    }
+                }
+            }
+            ThemeName::Monochrome => {
+                return None;
+            }
+        };
+        return Some(shade);
+    }
+}
+
+/// Colors the game is drawn with, the theme's shades shown at the color depth of the terminal.
+/// Both can be set in the [display] section of the config file, the color depth is detected from
+/// the terminal when it is not set.
+/// # Examples
+/// '''
+/// [display]
+/// theme = colorblind
+/// colors = 256
+/// '''
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Theme {
+    name: ThemeName,
+    depth: ColorDepth
+}
+
+impl Theme {
+    pub(crate) fn new(name :ThemeName, depth :ColorDepth) -> Theme {
+        return Theme { name, depth };
+    }
+
+    /// Theme and color depth of the config file's [display] section, the default theme at the
+    /// detected color depth for settings which are not given. Returns an error if a setting can not
+    /// be read
+    pub(crate) fn from_config(config :&ConfigFile) -> Result<Theme, GameError> {
+        let mut theme = Theme::new(ThemeName::Default, ColorDepth::detect());
+        for entry in config.section("display") {
+            match entry.name.as_str(){
+                "theme" => {
+                    theme.name = find_named(ThemeName::iter(), ThemeName::get_name, &entry.value)
+                        .map_err(|message| config.error(Some(entry.line), &format!("unknown theme '{}', {}", entry.value, message)))?;
+                }
+                "colors" if entry.value == "auto" => {
+                    theme.depth = ColorDepth::detect();
+                }
+                "colors" => {
+                    theme.depth = find_named(ColorDepth::iter(), ColorDepth::get_name, &entry.value)
+                        .map_err(|message| config.error(Some(entry.line), &format!("unknown colors '{}', {} or auto", entry.value, message)))?;
+                }
+                _ => {
+                    return Err(config.error(Some(entry.line), &format!("unknown display setting '{}'", entry.name)));
+                }
+            }
+        }
+        return Ok(theme);
+    }
+
+    /// Escape sequence switching the foreground to the role's color, empty if the theme leaves the
+    /// role uncolored or the terminal shows no colors
+    pub(crate) fn fg(&self, role :Role) -> String {
+        let shade = match self.name.shade(role) {
+            Some(shade) => { shade }
+            None => { return String::new(); }
+        };
+        return match (shade, self.depth) {
+            (_, ColorDepth::NoColor) => { String::new() }
+            (Shade::Basic(basic), _) | (Shade::Rgb(_, _, _, basic), ColorDepth::Ansi16) => { basic_fg(basic) }
+            (Shade::Rgb(r, g, b, _), ColorDepth::Ansi256) => { color::Fg(color::AnsiValue(palette_index(r, g, b))).to_string() }
+            (Shade::Rgb(r, g, b, _), ColorDepth::TrueColor) => { color::Fg(color::Rgb(r, g, b)).to_string() }
+        };
+    }
+}
+
+/// Returns the item with the given name, or else an error listing the names to choose from
+fn find_named<T>(mut items :impl Iterator<Item = T> + Clone, get_name :fn(&T) -> &'static str, name :&str) -> Result<T, String> {
+    let names = items.clone().map(|item| get_name(&item)).collect::<Vec<&str>>().join(", ");
+    return items.find(|item| get_name(item) == name.to_lowercase())
+        .ok_or_else(|| format!("expected one of {}", names));
+}
+
+/// Escape sequence for one of the 16 basic colors, written as the 8 color sequences every color
+/// terminal understands
+fn basic_fg(basic :u8) -> String {
+    let code = if basic < 8 { 30 + basic as u32 } else { 90 + (basic as u32 % 8) };
+    return format!("\x1b[{}m", code);
+}
+
+/// Returns the color of the 256 color palette's 6x6x6 color cube which is closest to the RGB color
+fn palette_index(r :u8, g :u8, b :u8) -> u8 {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let level = |value :u8| {
+        return (0..LEVELS.len())
+            .min_by_key(|index| (LEVELS[*index] as i32 - value as i32).abs())
+            .unwrap_or(0) as u8;
+    };
+    return 16 + 36 * level(r) + 6 * level(g) + level(b);
+}
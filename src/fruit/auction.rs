@@ -0,0 +1,206 @@
+use crate::fruit::{FruitOffer, OfferKind};
+use crate::config::Config;
+use crate::game::error::GameError;
+use crate::game::money::Money;
+
+/// Lot of fruit put up for auction, the rival bidder will not bid above rival_limit
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct AuctionLot {
+    pub(crate) lot_size: u32,
+    pub(crate) increment: Money,
+    pub(crate) rival_limit: Money
+}
+
+/// Bidders taking part in an auction
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum Bidder {
+    Player,
+    Rival
+}
+
+/// Bidding state of an auction offer. The offer's price is the opening bid and each following bid
+/// raises the top bid by the lot's increment. The rival bidder opens after
+/// Config::AUCTION_RIVAL_OPEN_PERCENT of the offer's duration and answers the player's bids after
+/// Config::AUCTION_RIVAL_REACTION_MILLIS as long as the bid stays within its limit.
+/// Whoever holds the top bid when the offer times out wins the lot.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct Auction {
+    lot: AuctionLot,
+    opening_bid: Money,
+    duration_ms: u32,
+    top_bid: Option<(Bidder, Money)>,
+    last_bid_ms: u32
+}
+
+impl Auction {
+    /// Creates auction state for the offer, returns None if the offer is not an auction
+    pub(crate) fn new(offer :&FruitOffer) -> Option<Auction> {
+        match offer.get_kind() {
+            OfferKind::Auction(lot) => {
+                Some(Auction{
+                    lot,
+                    opening_bid: offer.get_price(),
+                    duration_ms: offer.get_duration_ms(),
+                    top_bid: None,
+                    last_bid_ms: 0
+                })
+            }
+            _ => { None }
+        }
+    }
+
+    pub(crate) fn get_lot_size(&self) -> u32 {
+        return self.lot.lot_size;
+    }
+
+    /// Returns current top bidder and bid, None if nobody has bid yet
+    pub(crate) fn get_top_bid(&self) -> Option<(Bidder, Money)> {
+        return self.top_bid;
+    }
+
+    /// Amount the next bid has to be, bids can not be raised past the largest amount of money
+    pub(crate) fn get_next_bid(&self) -> Money {
+        match self.top_bid {
+            None => { self.opening_bid }
+            Some((_, bid)) => { bid.checked_add(self.lot.increment).unwrap_or(bid) }
+        }
+    }
+
+    /// Lowest bid the lot can be won with. The rival answers a bid only after
+    /// Config::AUCTION_RIVAL_REACTION_MILLIS, so once the rival has opened, outbidding it right
+    /// before the offer expires wins. When the opening bid is above the rival's limit the rival never
+    /// bids and the opening bid wins
+    pub(crate) fn get_lowest_winning_bid(&self) -> Money {
+        if self.opening_bid > self.lot.rival_limit {
+            return self.opening_bid;
+        }
+        return self.opening_bid.checked_add(self.lot.increment).unwrap_or(self.opening_bid);
+    }
+
+    pub(crate) fn is_player_top_bidder(&self) -> bool {
+        return matches!(self.top_bid, Some((Bidder::Player, _)));
+    }
+
+    /// Place the next bid for the player. Returns AlreadyTopBidder if the player already holds the
+    /// top bid, InsufficientFunds if the player can not afford the bid, else returns the bid placed
+    pub(crate) fn bid(&mut self, cash :Money, elapsed_ms :u32) -> Result<Money, GameError> {
+        if self.is_player_top_bidder() {
+            return Err(GameError::AlreadyTopBidder);
+        }
+        let bid = self.get_next_bid();
+        if bid > cash {
+            return Err(GameError::InsufficientFunds);
+        }
+        self.top_bid = Some((Bidder::Player, bid));
+        self.last_bid_ms = elapsed_ms;
+        return Ok(bid);
+    }
+
+    /// Let the rival bidder act. Returns true if the rival placed a bid
+    pub(crate) fn tick(&mut self, elapsed_ms :u32) -> bool {
+        if elapsed_ms >= self.duration_ms || self.get_next_bid() > self.lot.rival_limit {
+            return false;
+        }
+        let should_bid = match self.top_bid {
+            None => {
+                elapsed_ms >= self.duration_ms / 100 * Config::AUCTION_RIVAL_OPEN_PERCENT
+            }
+            Some((Bidder::Player, _)) => {
+                elapsed_ms.saturating_sub(self.last_bid_ms) >= Config::AUCTION_RIVAL_REACTION_MILLIS
+            }
+            Some((Bidder::Rival, _)) => { false }
+        };
+        if should_bid {
+            self.top_bid = Some((Bidder::Rival, self.get_next_bid()));
+            self.last_bid_ms = elapsed_ms;
+        }
+        return should_bid;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fruit::Fruit;
+    use crate::fruit::quality::Quality;
+
+    const DURATION_MS: u32 = 4000;
+
+    fn auction(opening_bid :u64, increment :u64, rival_limit :u64) -> Auction {
+        let lot = AuctionLot { lot_size: 5, increment: Money::from_dollars(increment), rival_limit: Money::from_dollars(rival_limit) };
+        let opening_bid = Money::from_dollars(opening_bid);
+        let offer = FruitOffer::scripted(0, Fruit::Apples, Quality::B, opening_bid, opening_bid, OfferKind::Auction(lot));
+        return Auction::new(&FruitOffer { duration_ms: DURATION_MS, ..offer }).unwrap();
+    }
+
+    #[test]
+    fn rival_opens_after_its_share_of_the_duration() {
+        let mut auction = auction(10, 1, 20);
+        let opens_at = DURATION_MS / 100 * Config::AUCTION_RIVAL_OPEN_PERCENT;
+        assert!(!auction.tick(opens_at - 1));
+        assert_eq!(auction.get_top_bid(), None);
+        assert!(auction.tick(opens_at));
+        assert_eq!(auction.get_top_bid(), Some((Bidder::Rival, Money::from_dollars(10))));
+    }
+
+    #[test]
+    fn rival_does_not_outbid_itself() {
+        let mut auction = auction(10, 1, 20);
+        let mut elapsed = 0;
+        while elapsed < DURATION_MS {
+            auction.tick(elapsed);
+            elapsed += Config::RENDER_TICK_MILLIS;
+        }
+        assert_eq!(auction.get_top_bid(), Some((Bidder::Rival, Money::from_dollars(10))));
+    }
+
+    #[test]
+    fn rival_with_a_limit_below_the_opening_bid_never_bids() {
+        let mut auction = auction(10, 1, 9);
+        assert!(!auction.tick(DURATION_MS - 1));
+        assert_eq!(auction.get_top_bid(), None);
+        assert_eq!(auction.get_lowest_winning_bid(), Money::from_dollars(10));
+    }
+
+    #[test]
+    fn bids_step_up_by_the_increment() {
+        let mut auction = auction(10, 2, 20);
+        let cash = Money::from_dollars(100);
+        assert_eq!(auction.bid(cash, 100).unwrap(), Money::from_dollars(10));
+        assert!(matches!(auction.bid(cash, 150), Err(GameError::AlreadyTopBidder)));
+        assert!(!auction.tick(100 + Config::AUCTION_RIVAL_REACTION_MILLIS - 1));
+        assert!(auction.tick(100 + Config::AUCTION_RIVAL_REACTION_MILLIS));
+        assert_eq!(auction.get_top_bid(), Some((Bidder::Rival, Money::from_dollars(12))));
+        assert_eq!(auction.bid(cash, 1000).unwrap(), Money::from_dollars(14));
+        assert!(matches!(auction.bid(Money::from_dollars(15), 1000), Err(GameError::AlreadyTopBidder)));
+    }
+
+    #[test]
+    fn rival_stops_at_its_limit() {
+        let mut auction = auction(10, 2, 15);
+        let cash = Money::from_dollars(100);
+        let mut elapsed = 0;
+        while elapsed < DURATION_MS {
+            if !auction.is_player_top_bidder() {
+                auction.bid(cash, elapsed).unwrap();
+            }
+            auction.tick(elapsed);
+            elapsed += Config::RENDER_TICK_MILLIS;
+        }
+        //Rival bid 12 and 16 would be past its limit, so the player's 14 wins
+        assert_eq!(auction.get_top_bid(), Some((Bidder::Player, Money::from_dollars(14))));
+        assert!(!auction.tick(DURATION_MS));
+    }
+
+    #[test]
+    fn rival_may_bid_exactly_its_limit() {
+        let mut auction = auction(10, 2, 12);
+        let cash = Money::from_dollars(100);
+        auction.bid(cash, 0).unwrap();
+        assert!(auction.tick(Config::AUCTION_RIVAL_REACTION_MILLIS));
+        assert_eq!(auction.get_top_bid(), Some((Bidder::Rival, Money::from_dollars(12))));
+        auction.bid(cash, 1000).unwrap();
+        assert!(!auction.tick(1000 + Config::AUCTION_RIVAL_REACTION_MILLIS));
+        assert_eq!(auction.get_top_bid(), Some((Bidder::Player, Money::from_dollars(14))));
+    }
+}
@@ -0,0 +1,32 @@
+use crate::game::money::Money;
+
+/// Lot of fruit sold as a whole at the offer's price, the lot is bought all at once or not at all
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct BulkLot {
+    pub(crate) lot_size: u32
+}
+
+impl BulkLot {
+    /// Price of one fruit in the lot, used to compare the lot against single fruit offers
+    pub(crate) fn unit_price(&self, lot_price :Money) -> f64 {
+        return lot_price.as_dollars() / self.lot_size.max(1) as f64;
+    }
+}
+
+/// Volume discount on a single fruit offer, once threshold fruits have been bought from the offer
+/// every following fruit costs discounted_price
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct TieredPrice {
+    pub(crate) threshold: u32,
+    pub(crate) discounted_price: Money
+}
+
+impl TieredPrice {
+    /// Price of the next fruit given how many fruits have already been bought from the offer
+    pub(crate) fn unit_price(&self, price :Money, units_bought :u32) -> Money {
+        if units_bought >= self.threshold {
+            return self.discounted_price;
+        }
+        return price;
+    }
+}
@@ -1,42 +1,93 @@
+use std::time::Instant;
+
 use crate::fruit::FruitOffer;
+use crate::fruit::auction::Auction;
 
-/// Keep track of current offer and creating offer clones
+/// Keep track of current offer and creating offer clones.
+/// When the current offer is an auction the controller also holds the auction's bidding state, and
+/// it counts the fruit bought from the current offer for volume discounts.
+/// While paused the current offer's clock is frozen, time spent paused is added to the offer's start
+/// on resume so the offer continues with the duration it had left
 pub(crate) struct FruitOfferController {
-    offer: Option<FruitOffer>
+    offer: Option<FruitOffer>,
+    auction: Option<Auction>,
+    units_bought: u32,
+    paused_at: Option<Instant>
 }
 
 impl FruitOfferController {
     pub(crate) fn new()-> FruitOfferController {
-        return FruitOfferController { offer: None };
+        return FruitOfferController { offer: None, auction: None, units_bought: 0, paused_at: None };
+    }
+
+    pub(crate) fn is_paused(&self) ->bool{
+        return self.paused_at.is_some();
+    }
+
+    /// Freeze the current offer's clock
+    pub(crate) fn pause(&mut self){
+        if self.paused_at.is_none() {
+            self.paused_at = Some(Instant::now());
+        }
     }
 
-    pub(crate) fn has_offer(&self) ->bool{
-        return match self.offer {
-            None => { false }
-            Some(_) => { true }
+    /// Continue the current offer's clock from where it was paused
+    pub(crate) fn resume(&mut self){
+        if let Some(paused_at) = self.paused_at.take() {
+            if let Some(offer) = &mut self.offer {
+                offer.start += paused_at.elapsed();
+            }
         }
     }
 
+    /// Set current offer, any auction of the previous offer should be taken with take_auction
+    /// before a new offer is set
     pub(crate) fn set_offer(&mut self, offer :&FruitOffer){
-        self.offer = Some(offer.clone());
+        self.offer = Some(*offer);
+        self.auction = Auction::new(offer);
+        self.units_bought = 0;
+    }
+
+    /// Amount of fruit bought from the current offer
+    pub(crate) fn get_units_bought(&self) ->u32{
+        return self.units_bought;
+    }
+
+    /// Count fruit bought from the current offer
+    pub(crate) fn add_units_bought(&mut self, units :u32){
+        self.units_bought += units;
+    }
+
+    /// Returns bidding state of the current offer, None if the current offer is not an auction
+    pub(crate) fn get_auction(&self) ->Option<&Auction>{
+        return self.auction.as_ref();
+    }
+
+    /// Returns bidding state of the current offer to bid on, None if the current offer is not an auction
+    pub(crate) fn get_auction_mut(&mut self) ->Option<&mut Auction>{
+        return self.auction.as_mut();
+    }
+
+    /// Removes and returns bidding state of the current offer, used to settle an auction when its
+    /// offer has ended or to forfeit it when the player skips the offer
+    pub(crate) fn take_auction(&mut self) ->Option<Auction>{
+        return self.auction.take();
     }
 
     /// Returns None if Controller has no option else creates
     /// clone of the current offer
     pub(crate) fn get_offer(&self) ->Option<FruitOffer>{
-        match &self.offer{
-            None => {
-                None
-            }
-            Some(offer) => {
-                Some(FruitOffer{
-                    fruit: offer.fruit.clone(),
-                    price: offer.price,
-                    start: offer.start.clone(),
-                    duration_ms: offer.duration_ms,
-                    id: offer.id
-                })
-            }
-        }
+        self.offer.as_ref().map(|offer| FruitOffer{
+            fruit: offer.fruit,
+            quality: offer.quality,
+            price: offer.price,
+            base_price: offer.base_price,
+            start: offer.start,
+            duration_ms: offer.duration_ms,
+            id: offer.id,
+            kind: offer.kind,
+            season: offer.season,
+            event: offer.event
+        })
     }
 }
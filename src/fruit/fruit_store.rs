@@ -1,77 +1,208 @@
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::thread;
-use std::time::{SystemTime, Duration};
-use std::sync::{Arc, mpsc};
-use rand::{thread_rng, Rng};
+use std::time::Instant;
+use std::ops::RangeInclusive;
+use rand::{thread_rng, Rng, SeedableRng};
+use rand::distributions::WeightedIndex;
+use rand::rngs::StdRng;
+use rand::prelude::Distribution;
+use strum::IntoEnumIterator;
 
-use crate::fruit::{Fruit, FruitOffer};
+use crate::fruit::{Fruit, FruitOffer, OfferKind};
+use crate::fruit::auction::AuctionLot;
+use crate::fruit::bulk::{BulkLot, TieredPrice};
+use crate::fruit::market_event::{ActiveMarketEvent, MarketEvents};
+use crate::fruit::season::Season;
+use crate::fruit::quality::Quality;
 use crate::config::Config;
+use crate::game::money::Money;
 
-/// Fruit store will create a new Fruit offer when last offer has expired
-/// creates a new thread for the Fruit store to live on.
-/// Fruit store thread will exit when either
-///     1. AMOUNT_OF_OFFERS defined in Config has occurred
-///     2. game_running AtomicBool is false, signalling that the game should exit
+/// Fruit store creates the game's offers one at a time, the game loop asks for a new offer when the
+/// last one has expired or the player skips it. The store runs out of offers after
+/// AMOUNT_OF_OFFERS defined in Config.
+/// All randomness comes from a generator seeded with Config::SEED, or a random seed when not set
 pub(crate) struct FruitStore {
-    keep_thread_alive: Arc<AtomicBool>
+    seed: u64,
+    rng: StdRng,
+    market_events: MarketEvents,
+    next_offer_id: u32
 }
 
 impl FruitStore {
     pub(crate) fn new()->FruitStore{
-        return FruitStore { keep_thread_alive: Arc::new(AtomicBool::new(true)) };
+        return FruitStore::with_seed(Config::SEED.unwrap_or_else(|| thread_rng().gen()));
     }
 
-    pub(crate) fn create_fruit_thread(&mut self, game_running :Arc<AtomicBool>,next_turn_requested :Arc<AtomicBool>, tx : mpsc::Sender<FruitOffer>){
-        let shared = Arc::clone(&self.keep_thread_alive);
-        thread::spawn(move || {
-            for offer_id in 0..Config::AMOUNT_OF_OFFERS {
-                let fruit: Fruit = rand::random();
-                let price= FruitStore::price_for_fruit(&fruit);
-
-                let mut rng = thread_rng();
-                let ms_offer_lasts = rng.gen_range(Config::OFFER_DURATION_MIN_MILLIS..Config::OFFER_DURATION_MAX_MILLIS);
-
-                let offer = FruitOffer{
-                    fruit,
-                    price,
-                    start: SystemTime::now(),
-                    duration_ms: ms_offer_lasts,
-                    id: offer_id
-                };
-                tx.send(offer).unwrap();
-
-                // Instead of sleeping entire duration in one step, split into 10ms durations so if
-                // player wishes to exit, then they get immediate response
-                let mut ms_slept = 0;
-                while ms_slept < ms_offer_lasts {
-                    if !game_running.load(Ordering::Relaxed) {
-                        break;
-                    }
-                    if next_turn_requested.load(Ordering::Relaxed) {
-                        next_turn_requested.swap(false, Ordering::Relaxed);
-                        break;
-                    }
-                    ms_slept = ms_slept + 10;
-                    thread::sleep(Duration::from_millis(10));
-                }
-
-                if !game_running.load(Ordering::Relaxed) {
-                    break;
-                }
+    /// Fruit store making the offers of the game played with seed
+    pub(crate) fn with_seed(seed :u64)->FruitStore{
+        return FruitStore {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            market_events: MarketEvents::new(Config::market_events()),
+            next_offer_id: 0
+        };
+    }
+
+    /// Seed used for the fruit store's offers and market events
+    pub(crate) fn get_seed(&self) -> u64 {
+        return self.seed;
+    }
+
+    /// Create the next offer, starting now. Returns None when all offers of the game have been made
+    pub(crate) fn next_offer(&mut self)->Option<FruitOffer>{
+        return self.next_offer_at(Instant::now());
+    }
+
+    /// Create the next offer starting at start, for games played on a fake clock. Returns None when
+    /// all offers of the game have been made
+    pub(crate) fn next_offer_at(&mut self, start :Instant)->Option<FruitOffer>{
+        if self.next_offer_id >= Config::AMOUNT_OF_OFFERS {
+            return None;
+        }
+        let offer_id = self.next_offer_id;
+        self.next_offer_id += 1;
+
+        let rng = &mut self.rng;
+        let season = Season::for_offer(offer_id);
+        let event = self.market_events.next_offer(rng);
+        let fruit = FruitStore::random_fruit(rng, &season, &event);
+        let base_price = FruitStore::price_for_fruit(rng, &fruit, &season, &event);
+        let quality = FruitStore::random_quality(rng);
+        let price = quality.buy_price(base_price);
+        let (kind, price) = FruitStore::random_kind(rng, price);
+
+        let ms_offer_lasts = rng.gen_range(Config::OFFER_DURATION_MIN_MILLIS..Config::OFFER_DURATION_MAX_MILLIS);
+
+        return Some(FruitOffer{
+            fruit,
+            quality,
+            price,
+            base_price,
+            start,
+            duration_ms: ms_offer_lasts,
+            id: offer_id,
+            kind,
+            season,
+            event
+        });
+    }
+
+    /// Create the next offer from a script rather than at random, a fixed price offer for the fruit
+    /// of the grade at the base price, starting now and lasting Config::TUTORIAL_OFFER_DURATION_MILLIS
+    pub(crate) fn scripted_offer(&mut self, fruit :Fruit, quality :Quality, base_price :Money)->FruitOffer{
+        let offer_id = self.next_offer_id;
+        self.next_offer_id += 1;
+        return FruitOffer{
+            fruit,
+            quality,
+            price: quality.buy_price(base_price),
+            base_price,
+            start: Instant::now(),
+            duration_ms: Config::TUTORIAL_OFFER_DURATION_MILLIS,
+            id: offer_id,
+            kind: OfferKind::Fixed,
+            season: Season::for_offer(offer_id),
+            event: None
+        };
+    }
+
+    /// Returns the price range for the fruit during the season with the active market event applied,
+    /// both ends included
+    pub(crate) fn range_for_fruit(fruit :&Fruit, season :&Season, event :&Option<ActiveMarketEvent>)->RangeInclusive<Money>{
+        let range = Config::range_for_fruit(fruit, season);
+        match event{
+            None => { range }
+            Some(event) => { event.range_for_fruit(fruit, range) }
+        }
+    }
+
+    /// Pick a base price to the cent from the fruit's price range
+    fn price_for_fruit(rng :&mut StdRng, fruit :&Fruit, season :&Season, event :&Option<ActiveMarketEvent>)->Money{
+        let range = FruitStore::range_for_fruit(fruit, season, event);
+        Money::from_cents(rng.gen_range(range.start().get_cents()..=range.end().get_cents()))
+    }
+
+    /// Pick a fruit using the seasonal appearance weights from Config::weight_for_fruit with the
+    /// active market event applied
+    fn random_fruit(rng :&mut StdRng, season :&Season, event :&Option<ActiveMarketEvent>)->Fruit{
+        let fruits: Vec<Fruit> = Fruit::iter().collect();
+        let weights = fruits.iter().map(|fruit| {
+            let weight = Config::weight_for_fruit(fruit, season);
+            match event{
+                None => { weight }
+                Some(event) => { event.weight_for_fruit(fruit, weight) }
             }
-            shared.swap(false, Ordering::Relaxed);
         });
+        return FruitStore::weighted_pick(rng, &fruits, weights);
+    }
+
+    /// Decide if the offer is a fixed price offer, an auctioned lot, a bulk lot or comes with a
+    /// volume discount. Returns the kind of offer together with its price, which for lots is the
+    /// price of the whole lot and for auctions the opening bid
+    fn random_kind(rng :&mut StdRng, price :Money)->(OfferKind, Money){
+        let roll = rng.gen_range(0..100);
+        if roll < Config::AUCTION_CHANCE_PERCENT {
+            return FruitStore::random_auction(rng, price);
+        }
+        if roll < Config::AUCTION_CHANCE_PERCENT + Config::BULK_CHANCE_PERCENT {
+            let lot = BulkLot{ lot_size: rng.gen_range(Config::BULK_LOT_MIN..=Config::BULK_LOT_MAX) };
+            return (OfferKind::Bulk(lot), price.saturating_mul(lot.lot_size).percent(Config::BULK_PRICE_PERCENT).max(Config::MIN_PRICE));
+        }
+        if roll < Config::AUCTION_CHANCE_PERCENT + Config::BULK_CHANCE_PERCENT + Config::TIERED_CHANCE_PERCENT {
+            let tiers = TieredPrice{
+                threshold: Config::TIERED_THRESHOLD,
+                discounted_price: price.percent(Config::TIERED_PRICE_PERCENT).max(Config::MIN_PRICE)
+            };
+            return (OfferKind::Tiered(tiers), price);
+        }
+        return (OfferKind::Fixed, price);
     }
 
-    pub(crate) fn price_for_fruit(fruit :&Fruit)->u32{
-        let mut rng = thread_rng();
-        rng.gen_range(Config::range_for_fruit(&fruit))
+    /// Create an auction of a lot, returns the auction together with its opening bid
+    fn random_auction(rng :&mut StdRng, price :Money)->(OfferKind, Money){
+        let lot_size = rng.gen_range(Config::AUCTION_LOT_MIN..=Config::AUCTION_LOT_MAX);
+        let lot_price = price.saturating_mul(lot_size);
+        let rival_percent = rng.gen_range(Config::AUCTION_RIVAL_LIMIT_MIN_PERCENT..=Config::AUCTION_RIVAL_LIMIT_MAX_PERCENT);
+        let lot = AuctionLot{
+            lot_size,
+            increment: lot_price.percent(Config::AUCTION_INCREMENT_PERCENT).max(Config::MIN_PRICE),
+            rival_limit: lot_price.percent(rival_percent)
+        };
+        return (OfferKind::Auction(lot), lot_price.percent(Config::AUCTION_OPENING_PERCENT).max(Config::MIN_PRICE));
     }
 
-    pub(crate) fn is_alive(&self) ->bool{
-        if self.keep_thread_alive.load(Ordering::Relaxed) {
-            return true;
+    /// Pick a quality grade using the weights from Config::weight_for_quality
+    fn random_quality(rng :&mut StdRng)->Quality{
+        let qualities: Vec<Quality> = Quality::iter().collect();
+        return FruitStore::weighted_pick(rng, &qualities, qualities.iter().map(Config::weight_for_quality));
+    }
+
+    /// Pick one of the items by their weights. Every weight counts as at least 1, so items scaled
+    /// down to a weight of 0 by a market event stay possible and the pick never runs out of items
+    fn weighted_pick<T :Copy>(rng :&mut StdRng, items :&[T], weights :impl Iterator<Item = u32>)->T{
+        let index = WeightedIndex::new(weights.map(|weight| weight.max(1)))
+            .expect("items always have a weight");
+        return items[index.sample(rng)];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weights_of_zero_still_pick_an_item() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let fruits: Vec<Fruit> = Fruit::iter().collect();
+        for _ in 0..100 {
+            let fruit = FruitStore::weighted_pick(&mut rng, &fruits, fruits.iter().map(|_| 0));
+            assert!(fruits.contains(&fruit));
         }
-        return false;
+    }
+
+    #[test]
+    fn zero_weight_is_as_likely_as_a_weight_of_one() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let picks: Vec<u32> = (0..1000).map(|_| FruitStore::weighted_pick(&mut rng, &[0, 1], vec![0, 1].into_iter())).collect();
+        let zeros = picks.iter().filter(|pick| **pick == 0).count();
+        assert!((400..600).contains(&zeros), "{} picks of 0", zeros);
     }
 }
@@ -0,0 +1,98 @@
+use std::ops::RangeInclusive;
+use rand::Rng;
+
+use crate::fruit::Fruit;
+use crate::config::Config;
+use crate::game::money::Money;
+
+/// Market event definition, an event changes the price range and how often a fruit is offered
+/// for a window of offers. Definitions are found in Config::market_events
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct MarketEvent {
+    /// Name of the headline announced on the news line while the event is active, the headlines
+    /// are found in the message catalogs' [events] section
+    pub(crate) name: &'static str,
+    /// Fruit affected by the event
+    pub(crate) fruit: Fruit,
+    /// Price range of the fruit is scaled by this percentage, 200 doubles the prices
+    pub(crate) price_percent: u32,
+    /// Appearance weight of the fruit is scaled by this percentage, 200 makes the fruit twice as common
+    pub(crate) weight_percent: u32,
+    /// Amount of offers the event lasts
+    pub(crate) duration_offers: u32
+}
+
+/// Market event currently affecting the fruit store together with how many offers it has left
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct ActiveMarketEvent {
+    event: MarketEvent,
+    offers_left: u32
+}
+
+impl ActiveMarketEvent {
+    /// Name of the event's headline in message catalogs, see Locale::event
+    pub(crate) fn get_name(&self) -> &'static str {
+        return self.event.name;
+    }
+
+    pub(crate) fn get_fruit(&self) -> Fruit {
+        return self.event.fruit;
+    }
+
+    /// Amount of offers left including the current one
+    pub(crate) fn get_offers_left(&self) -> u32 {
+        return self.offers_left;
+    }
+
+    /// Returns the price range of the fruit with the event applied. Fruits not affected by the
+    /// event keep their range. A range never starts below Config::MIN_PRICE and always contains at
+    /// least one price
+    pub(crate) fn range_for_fruit(&self, fruit :&Fruit, range :RangeInclusive<Money>) -> RangeInclusive<Money> {
+        if *fruit != self.event.fruit {
+            return range;
+        }
+        let start = range.start().percent(self.event.price_percent).max(Config::MIN_PRICE);
+        let end = range.end().percent(self.event.price_percent).max(start);
+        return start..=end;
+    }
+
+    /// Returns the appearance weight of the fruit with the event applied
+    pub(crate) fn weight_for_fruit(&self, fruit :&Fruit, weight :u32) -> u32 {
+        if *fruit != self.event.fruit {
+            return weight;
+        }
+        return weight * self.event.weight_percent / 100;
+    }
+}
+
+/// Keeps track of the currently active market event. Only one event is active at a time, a new
+/// event may fire with Config::MARKET_EVENT_CHANCE_PERCENT once the last one has ended.
+pub(crate) struct MarketEvents {
+    definitions: Vec<MarketEvent>,
+    active: Option<ActiveMarketEvent>
+}
+
+impl MarketEvents {
+    pub(crate) fn new(definitions :Vec<MarketEvent>) -> MarketEvents {
+        return MarketEvents { definitions, active: None };
+    }
+
+    /// Move the market one offer forward, ending the active event when it has run its course
+    /// and possibly firing a new one. Returns the event affecting the upcoming offer
+    pub(crate) fn next_offer<R: Rng + ?Sized>(&mut self, rng :&mut R) -> Option<ActiveMarketEvent> {
+        if let Some(active) = &mut self.active {
+            active.offers_left -= 1;
+            if active.offers_left == 0 {
+                self.active = None;
+            }
+        }
+
+        if self.active.is_none() && !self.definitions.is_empty()
+            && rng.gen_range(0..100) < Config::MARKET_EVENT_CHANCE_PERCENT {
+            let event = self.definitions[rng.gen_range(0..self.definitions.len())];
+            self.active = Some(ActiveMarketEvent { event, offers_left: event.duration_offers.max(1) });
+        }
+
+        return self.active;
+    }
+}
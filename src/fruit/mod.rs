@@ -1,51 +1,180 @@
 pub mod fruit_store;
 pub mod fruit_offer_controller;
+pub mod market_event;
+pub mod season;
+pub mod quality;
+pub mod auction;
+pub mod bulk;
+pub mod price_history;
 
 use rand::{distributions::{Distribution, Standard}, Rng};
-use std::time::SystemTime;
+use std::time::{Duration, Instant};
 use strum_macros::EnumIter;
 
-/// Fruit offer consists of only 1 fruit and 1 price
+use crate::config::Config;
+use crate::fruit::market_event::ActiveMarketEvent;
+use crate::fruit::season::Season;
+use crate::fruit::quality::Quality;
+use crate::fruit::auction::AuctionLot;
+use crate::fruit::bulk::{BulkLot, TieredPrice};
+use crate::game::action::PlayerAction;
+use crate::game::error::GameError;
+use crate::game::money::Money;
+
+/// Fruit offer consists of only 1 fruit and 1 price, unless the offer is a bulk lot in which case
+/// the price is for the whole lot or an auction in which case the price is the opening bid
+/// Price is the fruit's base price scaled by the offer's quality grade, the base price is kept
+/// since the store buys back fruit at a price depending on the grade being sold
 /// Offers will expire on duration_ms
+/// Season and market event which were active when the offer was created are carried along for display
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub(crate) struct FruitOffer{
     fruit :Fruit,
-    price :u32,
-    start :SystemTime,
+    quality :Quality,
+    price :Money,
+    base_price :Money,
+    start :Instant,
     duration_ms :u32,
-    id :u32
+    id :u32,
+    kind :OfferKind,
+    season :Season,
+    event :Option<ActiveMarketEvent>
 }
 
 impl FruitOffer {
     pub(crate) fn get_fruit(&self)->Fruit{
-        return self.fruit.clone();
+        return self.fruit;
+    }
+
+    pub(crate) fn get_quality(&self)->Quality{
+        return self.quality;
     }
 
-    pub(crate) fn get_price(&self) -> u32 {
+    pub(crate) fn get_price(&self) -> Money {
         return self.price;
     }
 
+    /// Price of the next purchase from the offer given how many fruits have been bought from it.
+    /// Only volume discounted offers change price
+    pub(crate) fn get_price_after(&self, units_bought :u32) -> Money {
+        match self.kind{
+            OfferKind::Tiered(tiers) => { tiers.unit_price(self.price, units_bought) }
+            _ => { self.price }
+        }
+    }
+
+    /// Amount of fruit the player receives when buying the offer
+    pub(crate) fn get_units(&self) -> u32 {
+        match self.kind{
+            OfferKind::Auction(lot) => { lot.lot_size }
+            OfferKind::Bulk(lot) => { lot.lot_size }
+            _ => { 1 }
+        }
+    }
+
+    pub(crate) fn get_base_price(&self) -> Money {
+        return self.base_price;
+    }
+
+    /// Returns true if the next purchase from the offer is at a volume discount given how many
+    /// fruits have been bought from it
+    pub(crate) fn is_discounted_after(&self, units_bought :u32) -> bool {
+        match self.kind{
+            OfferKind::Tiered(tiers) => { units_bought >= tiers.threshold }
+            _ => { false }
+        }
+    }
+
+    /// Base price the store buys fruit back at on this offer. Bulk lots are sold below the base
+    /// price, fruit is bought back at the same discount so a lot can not be sold back on its offer
+    /// for more than it cost
+    pub(crate) fn get_resale_base_price(&self) -> Money {
+        match self.kind{
+            OfferKind::Bulk(_) => { self.base_price.percent(Config::BULK_PRICE_PERCENT) }
+            _ => { self.base_price }
+        }
+    }
+
+    /// Base price the store buys back fruit bought on this offer at a volume discount at, the
+    /// discount is passed on so the fruit can not be sold back for more than it cost
+    pub(crate) fn get_discounted_resale_base_price(&self) -> Money {
+        match self.kind{
+            OfferKind::Tiered(_) => { self.base_price.percent(Config::TIERED_PRICE_PERCENT) }
+            _ => { self.get_resale_base_price() }
+        }
+    }
+
     pub(crate) fn millis_since_offer(&self) -> u32 {
-        return self.start.elapsed().unwrap().as_millis() as u32;
+        return self.millis_since_offer_at(Instant::now());
+    }
+
+    /// Milliseconds from the start of the offer until the given instant, 0 if the instant is
+    /// before the offer started
+    pub(crate) fn millis_since_offer_at(&self, at :Instant) -> u32 {
+        return at.saturating_duration_since(self.start).as_millis() as u32;
+    }
+
+    /// Instant at which the offer is no longer available
+    pub(crate) fn expires_at(&self) -> Instant {
+        return self.start + Duration::from_millis(self.duration_ms as u64);
+    }
+
+    /// Checks that the action was meant for this offer and was made before the offer expired.
+    /// Actions are applied some time after they are made, by then the offer they were meant for may
+    /// have expired or been replaced by the next one, such actions are rejected as expired
+    pub(crate) fn validate_action(&self, action :&PlayerAction) -> Result<(), GameError> {
+        if action.offer_id != Some(self.id) || action.at >= self.expires_at() {
+            return Err(GameError::OfferExpired);
+        }
+        return Ok(());
     }
 
     pub(crate) fn get_duration_as_percent_of_elapsed(&self, elapsed: u32) -> f64 {
-        let left;
-        if self.duration_ms < elapsed {
-            left = 1;
+        let left = if self.duration_ms < elapsed {
+            1
         }else{
-            left = self.duration_ms - elapsed;
-        }
+            self.duration_ms - elapsed
+        };
         return (left as f64 / self.duration_ms as f64)*100f64;
     }
 
+    pub(crate) fn get_duration_ms(&self) -> u32 {
+        return self.duration_ms;
+    }
+
     pub(crate) fn get_id(&self)->u32{
         return self.id;
     }
+
+    pub(crate) fn get_kind(&self)->OfferKind{
+        return self.kind;
+    }
+
+    pub(crate) fn get_season(&self)->Season{
+        return self.season;
+    }
+
+    pub(crate) fn get_event(&self)->Option<ActiveMarketEvent>{
+        return self.event;
+    }
 }
 
-/// Five classic fruits, each with their own price range determined in FruitStore::range_for_fruit
+/// The different ways the fruit store offers fruit
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum OfferKind {
+    /// One fruit at a fixed price, take it or leave it
+    Fixed,
+    /// Lot of fruit auctioned against a rival bidder, see auction::Auction
+    Auction(AuctionLot),
+    /// Lot of fruit sold as a whole
+    Bulk(BulkLot),
+    /// One fruit at a time, cheaper after buying enough fruit from the offer
+    Tiered(TieredPrice)
+}
+
+/// Five classic fruits, each with their own seasonal price range determined in FruitStore::range_for_fruit
 #[derive(Clone, Copy, Debug, EnumIter, Eq, Hash, PartialEq)]
+#[allow(clippy::enum_variant_names)]
 pub(crate) enum Fruit {
     Apples,
     Banana,
@@ -54,6 +183,29 @@ pub(crate) enum Fruit {
     Elderberry
 }
 
+impl Fruit {
+    /// Name of the fruit in message catalogs, see Locale
+    pub(crate) fn get_key(&self) -> &'static str {
+        match self{
+            Fruit::Apples => {"apple"}
+            Fruit::Banana => {"banana"}
+            Fruit::Coconut => {"coconut"}
+            Fruit::DragonFruit => {"dragonfruit"}
+            Fruit::Elderberry => {"elderberry"}
+        }
+    }
+
+    pub(crate) fn get_name(&self) -> &'static str {
+        match self{
+            Fruit::Apples => {"Apple"}
+            Fruit::Banana => {"Banana"}
+            Fruit::Coconut => {"Coconut"}
+            Fruit::DragonFruit => {"DragonFruit"}
+            Fruit::Elderberry => {"Elderberry"}
+        }
+    }
+}
+
 /// Create a random fruit by using rand::random()
 /// # Examples
 /// '''
@@ -71,3 +223,72 @@ impl Distribution<Fruit> for Standard {
     }
 }
 
+/// Offers built by hand for tests, with the offer's price given rather than drawn by the fruit store
+#[cfg(test)]
+impl FruitOffer {
+    pub(crate) fn scripted(id :u32, fruit :Fruit, quality :Quality, price :Money, base_price :Money, kind :OfferKind) -> FruitOffer {
+        return FruitOffer {
+            fruit,
+            quality,
+            price,
+            base_price,
+            start: Instant::now(),
+            duration_ms: Config::TUTORIAL_OFFER_DURATION_MILLIS,
+            id,
+            kind,
+            season: Season::for_offer(id),
+            event: None
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::console::input::PlayerInteractions;
+
+    fn offer(id :u32, start :Instant) -> FruitOffer {
+        let price = Money::from_dollars(10);
+        return FruitOffer { start, duration_ms: 1000, ..FruitOffer::scripted(id, Fruit::Apples, Quality::B, price, price, OfferKind::Fixed) };
+    }
+
+    fn action(offer_id :Option<u32>, at :Instant) -> PlayerAction {
+        return PlayerAction { interaction: PlayerInteractions::Buy, offer_id, at };
+    }
+
+    #[test]
+    fn action_just_before_expiry_is_valid() {
+        let start = Instant::now();
+        let offer = offer(3, start);
+        assert!(offer.validate_action(&action(Some(3), start)).is_ok());
+        assert!(offer.validate_action(&action(Some(3), start + Duration::from_millis(999))).is_ok());
+    }
+
+    #[test]
+    fn action_at_or_after_expiry_is_expired() {
+        let start = Instant::now();
+        let offer = offer(3, start);
+        let at_expiry = offer.validate_action(&action(Some(3), start + Duration::from_millis(1000)));
+        assert!(matches!(at_expiry, Err(GameError::OfferExpired)));
+        let after_expiry = offer.validate_action(&action(Some(3), start + Duration::from_millis(1001)));
+        assert!(matches!(after_expiry, Err(GameError::OfferExpired)));
+    }
+
+    #[test]
+    fn action_meant_for_another_offer_is_expired() {
+        let start = Instant::now();
+        let offer = offer(3, start);
+        let previous = offer.validate_action(&action(Some(2), start + Duration::from_millis(10)));
+        assert!(matches!(previous, Err(GameError::OfferExpired)));
+        let before_first = offer.validate_action(&action(None, start + Duration::from_millis(10)));
+        assert!(matches!(before_first, Err(GameError::OfferExpired)));
+    }
+
+    #[test]
+    fn millis_since_offer_is_zero_before_start() {
+        let start = Instant::now() + Duration::from_millis(50);
+        let offer = offer(0, start);
+        assert_eq!(offer.millis_since_offer_at(start - Duration::from_millis(20)), 0);
+        assert_eq!(offer.millis_since_offer_at(start + Duration::from_millis(20)), 20);
+    }
+}
@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+
+use crate::fruit::{Fruit, FruitOffer};
+
+/// Every price the fruit store offered per fruit, in the order of the offers. Prices are kept per
+/// fruit so lots and auctions are recorded at their price per fruit, comparable to single fruit
+/// offers
+pub(crate) struct PriceHistory {
+    prices: HashMap<Fruit, Vec<f64>>
+}
+
+/// Lowest, average and highest price offered for a fruit
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct PriceStats {
+    pub(crate) min: f64,
+    pub(crate) average: f64,
+    pub(crate) max: f64
+}
+
+impl PriceHistory {
+    pub(crate) fn new() -> PriceHistory {
+        return PriceHistory { prices: HashMap::new() };
+    }
+
+    /// Record the price per fruit of the offer, auctions are recorded at their opening bid
+    pub(crate) fn record(&mut self, offer :&FruitOffer) {
+        let unit_price = offer.get_price().as_dollars() / offer.get_units().max(1) as f64;
+        self.prices.entry(offer.get_fruit()).or_default().push(unit_price);
+    }
+
+    /// Prices offered for the fruit from the first offer to the last
+    pub(crate) fn get_prices(&self, fruit :Fruit) -> &[f64] {
+        return self.prices.get(&fruit).map(Vec::as_slice).unwrap_or(&[]);
+    }
+
+    /// Returns None if the fruit has not been offered yet
+    pub(crate) fn get_stats(&self, fruit :Fruit) -> Option<PriceStats> {
+        let prices = self.get_prices(fruit);
+        if prices.is_empty() {
+            return None;
+        }
+        let min = prices.iter().cloned().fold(f64::MAX, f64::min);
+        let max = prices.iter().cloned().fold(f64::MIN, f64::max);
+        let average = prices.iter().sum::<f64>() / prices.len() as f64;
+        return Some(PriceStats { min, average, max });
+    }
+}
@@ -0,0 +1,59 @@
+use strum_macros::EnumIter;
+
+use crate::config::Config;
+use crate::game::money::Money;
+
+/// Quality grade of an offer, A being premium and C being close to overripe.
+/// Grade scales the price of an offer, how long the fruit keeps and how much the store pays when
+/// the fruit is sold back, see Config::price_percent_for_quality and friends
+#[derive(Clone, Copy, Debug, EnumIter, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub(crate) enum Quality {
+    A,
+    B,
+    C
+}
+
+impl Quality {
+    /// Scale a base price by a percentage, a price never goes below Config::MIN_PRICE
+    pub(crate) fn scale_price(base_price :Money, percent :u32) -> Money {
+        return base_price.percent(percent).max(Config::MIN_PRICE);
+    }
+
+    /// Price the store asks for fruit of this grade given the base price of the fruit
+    pub(crate) fn buy_price(&self, base_price :Money) -> Money {
+        return Quality::scale_price(base_price, Config::price_percent_for_quality(self));
+    }
+
+    /// Price the store pays for fruit of this grade given the base price of the fruit. Unlike the
+    /// price asked it is not raised to Config::MIN_PRICE, so it stays below the price asked
+    pub(crate) fn sell_price(&self, base_price :Money) -> Money {
+        return base_price.percent(Config::resale_percent_for_quality(self));
+    }
+
+    pub(crate) fn get_name(&self) -> &'static str {
+        match self{
+            Quality::A => {"A"}
+            Quality::B => {"B"}
+            Quality::C => {"C"}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn store_pays_less_than_it_asks_for_every_grade() {
+        //Cheapest prices, around the minimum price the store asks and typical fruit prices
+        let prices = [1, 2, 5, 9, 10, 11, 20, 99, 100, 101, 250, 333, 999, 1000, 4567, 10_000];
+        for quality in Quality::iter() {
+            for cents in prices.iter().copied() {
+                let base_price = Money::from_cents(cents);
+                assert!(quality.sell_price(base_price) < quality.buy_price(base_price),
+                        "grade {} at base price {}", quality.get_name(), base_price);
+            }
+        }
+    }
+}
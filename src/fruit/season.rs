@@ -0,0 +1,42 @@
+use strum_macros::EnumIter;
+
+use crate::config::Config;
+
+/// The year is split into four seasons which cycle during the game, each lasting
+/// Config::SEASON_LENGTH_OFFERS offers. Fruits are cheaper and more common while in season.
+#[derive(Clone, Copy, Debug, EnumIter, Eq, Hash, PartialEq)]
+pub(crate) enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter
+}
+
+impl Season {
+    /// Returns the season the offer in question falls into, starting with spring
+    pub(crate) fn for_offer(offer_id :u32) -> Season {
+        let length = Config::SEASON_LENGTH_OFFERS.max(1);
+        match (offer_id / length) % 4 {
+            0 => Season::Spring,
+            1 => Season::Summer,
+            2 => Season::Autumn,
+            _ => Season::Winter
+        }
+    }
+
+    /// Amount of offers left of the season, including the offer in question
+    pub(crate) fn offers_left(offer_id :u32) -> u32 {
+        let length = Config::SEASON_LENGTH_OFFERS.max(1);
+        return length - offer_id % length;
+    }
+
+    /// Name of the season in message catalogs, see Locale
+    pub(crate) fn get_key(&self) -> &'static str {
+        match self{
+            Season::Spring => {"spring"}
+            Season::Summer => {"summer"}
+            Season::Autumn => {"autumn"}
+            Season::Winter => {"winter"}
+        }
+    }
+}
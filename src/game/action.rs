@@ -0,0 +1,38 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Instant;
+
+use crate::console::input::PlayerInteractions;
+
+/// Interaction together with the offer the player meant it for and when it was made. Actions are
+/// validated against the current offer when applied, see FruitOffer::validate_action, so an action
+/// meant for an offer which has since expired is never applied to the next one
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct PlayerAction {
+    pub(crate) interaction: PlayerInteractions,
+    /// Offer shown when the action was made, None if no offer was shown yet
+    pub(crate) offer_id: Option<u32>,
+    pub(crate) at: Instant
+}
+
+/// Id of the offer currently shown to the player. Shared between the game loop, which sets it when
+/// showing a new offer, and the input thread, which stamps each key with it
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ShownOffer {
+    //Offer id + 1, 0 when no offer is shown
+    id: Arc<AtomicU32>
+}
+
+impl ShownOffer {
+    pub(crate) fn new() -> ShownOffer {
+        return ShownOffer::default();
+    }
+
+    pub(crate) fn set(&self, offer_id :u32) {
+        self.id.store(offer_id + 1, Ordering::SeqCst);
+    }
+
+    pub(crate) fn get(&self) -> Option<u32> {
+        return self.id.load(Ordering::SeqCst).checked_sub(1);
+    }
+}
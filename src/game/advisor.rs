@@ -0,0 +1,216 @@
+use crate::bot::Situation;
+use crate::config::Config;
+use crate::fruit::price_history::PriceHistory;
+use crate::game::error::GameError;
+
+/// How good the current offer is for the player
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum Rating {
+    /// Buying is expected to pay off well, see Config::ADVISOR_GREAT_BUY_PERCENT
+    GreatBuy,
+    /// Buying is expected to pay off a little
+    Fair,
+    /// Buying is expected to lose money
+    Overpriced,
+    /// The store pays more for the fruit held than it is expected to pay later
+    SellNow
+}
+
+impl Rating {
+    /// Name of the rating in the message catalogs
+    pub(crate) fn get_name(&self) -> &'static str {
+        match self{
+            Rating::GreatBuy => {"great_buy"}
+            Rating::Fair => {"fair"}
+            Rating::Overpriced => {"overpriced"}
+            Rating::SellNow => {"sell_now"}
+        }
+    }
+}
+
+/// Rating of the current offer with its expected value in dollars per fruit. When buying, the
+/// value is what the fruit is expected to sell for later less its price, when selling now, it is
+/// what selling now gets more than selling later is expected to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Advice {
+    pub(crate) rating: Rating,
+    pub(crate) expected_value: f64
+}
+
+/// Hints for new players rating the current offer. The fruit's future price is expected between
+/// the middle of its price range and the average price it has been offered at so far, fruit is
+/// expected to sell for what the store pays for its grade at that price.
+/// The advisor is shown and hidden by the player, in ranked games it can not be shown so scores
+/// are made without its help. Games in which it was shown are marked at game over
+pub(crate) struct Advisor {
+    shown: bool,
+    used: bool,
+    ranked: bool
+}
+
+impl Advisor {
+    pub(crate) fn new(ranked :bool) -> Advisor {
+        return Advisor { shown: false, used: false, ranked };
+    }
+
+    /// Show or hide the advice, returns whether the advice is shown. Returns AdvisorDisabled in
+    /// ranked games
+    pub(crate) fn toggle(&mut self) -> Result<bool, GameError> {
+        if self.ranked {
+            return Err(GameError::AdvisorDisabled);
+        }
+        self.shown = !self.shown;
+        self.used |= self.shown;
+        return Ok(self.shown);
+    }
+
+    /// Returns true if the advisor has been shown during the game
+    pub(crate) fn was_used(&self) -> bool {
+        return self.used;
+    }
+
+    /// Advice for the situation, None while the advisor is hidden
+    pub(crate) fn advise(&self, situation :&Situation, history :&PriceHistory) -> Option<Advice> {
+        if !self.shown {
+            return None;
+        }
+        let midpoint = situation.get_midpoint().as_dollars();
+        let expected_price = match history.get_stats(situation.offer.get_fruit()) {
+            None => { midpoint }
+            Some(stats) => { (midpoint + stats.average) / 2.0 }
+        };
+        //Expected price is of the base price, the store pays a share of it depending on the grade
+        let resale = |percent :u32| expected_price * percent as f64 / 100.0;
+
+        if let (Some(sell_price), Some(quality)) = (situation.get_sell_price(), situation.get_best_held_quality()) {
+            let gain = sell_price.as_dollars() - resale(Config::resale_percent_for_quality(&quality));
+            if gain > 0.0 {
+                return Some(Advice { rating: Rating::SellNow, expected_value: gain });
+            }
+        }
+        let unit_price = situation.get_lot_price().as_dollars() / situation.offer.get_units().max(1) as f64;
+        let expected_value = resale(Config::resale_percent_for_quality(&situation.offer.get_quality())) - unit_price;
+        let rating = if expected_value >= unit_price * Config::ADVISOR_GREAT_BUY_PERCENT as f64 / 100.0 {
+            Rating::GreatBuy
+        } else if expected_value >= 0.0 {
+            Rating::Fair
+        } else {
+            Rating::Overpriced
+        };
+        return Some(Advice { rating, expected_value });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fruit::{Fruit, FruitOffer, OfferKind};
+    use crate::fruit::bulk::BulkLot;
+    use crate::fruit::quality::Quality;
+    use crate::game::money::Money;
+    use crate::player::Player;
+
+    fn apples(price :Money, base_price :Money, kind :OfferKind) -> FruitOffer {
+        return FruitOffer::scripted(0, Fruit::Apples, Quality::B, price, base_price, kind);
+    }
+
+    /// Situation of the offer with apples being priced between $1 and $3
+    fn situation<'a>(offer :&'a FruitOffer, player :&'a Player) -> Situation<'a> {
+        return Situation {
+            offer,
+            range: Money::from_dollars(1)..=Money::from_dollars(3),
+            auction: None,
+            units_bought: 0,
+            player,
+            offers_left: 10
+        };
+    }
+
+    /// Advice of a shown advisor for the offer
+    fn advise(offer :&FruitOffer, player :&Player, history :&PriceHistory) -> Advice {
+        let mut advisor = Advisor::new(false);
+        advisor.toggle().unwrap();
+        return advisor.advise(&situation(offer, player), history).unwrap();
+    }
+
+    /// Rating of a single grade B apple for the price, expected to sell for $1.80 later
+    fn rating(cents :u64) -> Rating {
+        let offer = apples(Money::from_cents(cents), Money::from_dollars(2), OfferKind::Fixed);
+        return advise(&offer, &Player::new(Config::STARTING_CASH), &PriceHistory::new()).rating;
+    }
+
+    #[test]
+    fn buying_is_rated_by_the_expected_resale_price() {
+        assert_eq!(rating(100), Rating::GreatBuy);
+        assert_eq!(rating(140), Rating::GreatBuy);
+        assert_eq!(rating(150), Rating::Fair);
+        assert_eq!(rating(180), Rating::Fair);
+        assert_eq!(rating(181), Rating::Overpriced);
+    }
+
+    #[test]
+    fn expected_value_is_per_fruit() {
+        let player = Player::new(Config::STARTING_CASH);
+        let single = apples(Money::from_cents(150), Money::from_dollars(2), OfferKind::Fixed);
+        let advice = advise(&single, &player, &PriceHistory::new());
+        assert!((advice.expected_value - 0.3).abs() < 1e-9);
+
+        let lot = apples(Money::from_cents(600), Money::from_dollars(2), OfferKind::Bulk(BulkLot { lot_size: 4 }));
+        assert_eq!(advise(&lot, &player, &PriceHistory::new()), advice);
+    }
+
+    #[test]
+    fn prices_offered_so_far_move_the_expected_price() {
+        let player = Player::new(Config::STARTING_CASH);
+        let offer = apples(Money::from_cents(140), Money::from_dollars(2), OfferKind::Fixed);
+        let mut history = PriceHistory::new();
+        history.record(&apples(Money::from_dollars(1), Money::from_dollars(1), OfferKind::Fixed));
+        //Expected price is halfway between the range's middle at $2 and the average of $1
+        let advice = advise(&offer, &player, &history);
+        assert_eq!(advice.rating, Rating::Overpriced);
+        assert!((advice.expected_value - (1.5 * 0.9 - 1.4)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fruit_held_is_sold_when_it_sells_for_more_now() {
+        let mut player = Player::new(Config::STARTING_CASH);
+        player.buy_lot(Fruit::Apples, Quality::B, 1, Money::from_dollars(1), 0, false).unwrap();
+
+        let dear = apples(Money::from_cents(220), Money::from_cents(220), OfferKind::Fixed);
+        let advice = advise(&dear, &player, &PriceHistory::new());
+        assert_eq!(advice.rating, Rating::SellNow);
+        assert!((advice.expected_value - (1.98 - 1.8)).abs() < 1e-9);
+
+        //Selling for what it is expected to sell for later gains nothing, buying more is rated instead
+        let expected = apples(Money::from_dollars(2), Money::from_dollars(2), OfferKind::Fixed);
+        assert_eq!(advise(&expected, &player, &PriceHistory::new()).rating, Rating::Overpriced);
+    }
+
+    #[test]
+    fn advice_is_given_only_while_shown() {
+        let player = Player::new(Config::STARTING_CASH);
+        let offer = apples(Money::from_dollars(1), Money::from_dollars(2), OfferKind::Fixed);
+        let situation = situation(&offer, &player);
+        let mut advisor = Advisor::new(false);
+        assert!(advisor.advise(&situation, &PriceHistory::new()).is_none());
+        assert!(!advisor.was_used());
+
+        assert!(advisor.toggle().unwrap());
+        assert!(advisor.advise(&situation, &PriceHistory::new()).is_some());
+        assert!(!advisor.toggle().unwrap());
+        assert!(advisor.advise(&situation, &PriceHistory::new()).is_none());
+        assert!(advisor.was_used());
+    }
+
+    #[test]
+    fn advisor_can_not_be_shown_in_ranked_games() {
+        let player = Player::new(Config::STARTING_CASH);
+        let offer = apples(Money::from_dollars(1), Money::from_dollars(2), OfferKind::Fixed);
+        let situation = situation(&offer, &player);
+        let mut advisor = Advisor::new(true);
+        assert!(matches!(advisor.toggle(), Err(GameError::AdvisorDisabled)));
+        assert!(matches!(advisor.toggle(), Err(GameError::AdvisorDisabled)));
+        assert!(advisor.advise(&situation, &PriceHistory::new()).is_none());
+        assert!(!advisor.was_used());
+    }
+}
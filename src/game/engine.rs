@@ -0,0 +1,145 @@
+use std::time::Instant;
+
+use crate::bot::Situation;
+use crate::config::Config;
+use crate::console::input::PlayerInteractions;
+use crate::console::key_handling::KeyHeldController;
+use crate::fruit::{Fruit, FruitOffer};
+use crate::fruit::auction::Auction;
+use crate::fruit::fruit_offer_controller::FruitOfferController;
+use crate::game::action::PlayerAction;
+use crate::game::error::GameError;
+use crate::player::Player;
+
+/// Rules of playing the game's offers, shared by the game loop and simulated games so both play by
+/// the same rules. The engine holds the current offer with its auction, the player and the keys
+/// held down. It is told the time instead of reading the clock, the game loop passes the time keys
+/// were pressed and render ticks happened, simulated games a fake clock.
+/// Interactions which are not about the offer, such as pausing or showing the help, are left to the
+/// game loop
+pub(crate) struct Engine {
+    offer_controller: FruitOfferController,
+    player: Player,
+    //Key held controller does so if players keeps holding a key to repeatable buy they do not
+    //mistakenly buy the next offer when the key held was started on the previous offer
+    key_held_controller: KeyHeldController
+}
+
+impl Engine {
+    pub(crate) fn new() -> Engine {
+        return Engine {
+            offer_controller: FruitOfferController::new(),
+            player: Player::new(Config::STARTING_CASH),
+            key_held_controller: KeyHeldController::new()
+        };
+    }
+
+    pub(crate) fn get_player(&self) -> &Player {
+        return &self.player;
+    }
+
+    pub(crate) fn get_offer_controller(&self) -> &FruitOfferController {
+        return &self.offer_controller;
+    }
+
+    /// Offer controller to pause and resume the current offer with
+    pub(crate) fn get_offer_controller_mut(&mut self) -> &mut FruitOfferController {
+        return &mut self.offer_controller;
+    }
+
+    /// Situation of the current offer as a strategy or the advisor sees it, offer being the
+    /// controller's offer
+    pub(crate) fn situation<'a>(&'a self, offer :&'a FruitOffer) -> Situation<'a> {
+        return Situation::new(offer, &self.offer_controller, &self.player);
+    }
+
+    /// Make the offer the current one, the auction of the previous offer should be settled with
+    /// settle_auction first. Returns the amount of each fruit which spoiled by the offer's arrival
+    pub(crate) fn start_offer(&mut self, offer :&FruitOffer) -> Vec<(Fruit,u32)> {
+        self.offer_controller.set_offer(offer);
+        return self.player.remove_spoiled(offer.get_id());
+    }
+
+    /// Let the current auction's rival act and release keys no longer held, now being the time of
+    /// the tick. Returns true if the rival placed a bid
+    pub(crate) fn tick(&mut self, now :Instant) -> bool {
+        self.key_held_controller.cancel_if_elapsed(now);
+        let elapsed = match self.offer_controller.get_offer() {
+            None => { return false; }
+            Some(offer) => { offer.millis_since_offer_at(now) }
+        };
+        return match self.offer_controller.get_auction_mut() {
+            None => { false }
+            Some(auction) => { auction.tick(elapsed) }
+        };
+    }
+
+    /// Interaction of the action unless it repeats a buy or sell held down since a former offer,
+    /// see KeyHeldController
+    pub(crate) fn filter_held(&mut self, action :&PlayerAction, offer_id :u32) -> Option<PlayerInteractions> {
+        return self.key_held_controller.filter_option_interaction(Some(action.interaction.clone()), offer_id, action.at);
+    }
+
+    /// Buy the current offer, or place the next bid when it is an auction, at the time of the action.
+    /// Returns OfferExpired if the action was not meant for the current offer or came too late, else
+    /// the error which kept the player from buying
+    pub(crate) fn buy(&mut self, action :&PlayerAction) -> Result<(), GameError> {
+        let offer = self.current_offer(action)?;
+        match self.offer_controller.get_auction_mut(){
+            Some(auction) => {
+                auction.bid(self.player.get_cash(), offer.millis_since_offer_at(action.at))?;
+            }
+            None => {
+                let units_bought = self.offer_controller.get_units_bought();
+                let price = offer.get_price_after(units_bought);
+                let discounted = offer.is_discounted_after(units_bought);
+                self.player.buy_lot(offer.get_fruit(), offer.get_quality(), offer.get_units(), price, offer.get_id(), discounted)?;
+                self.offer_controller.add_units_bought(offer.get_units());
+            }
+        }
+        return Ok(());
+    }
+
+    /// Sell one of the offered fruit to the store. Returns AuctionOnlyBids if the current offer is
+    /// an auction, else like buy
+    pub(crate) fn sell(&mut self, action :&PlayerAction) -> Result<(), GameError> {
+        let offer = self.current_offer(action)?;
+        if self.offer_controller.get_auction().is_some() {
+            return Err(GameError::AuctionOnlyBids);
+        }
+        self.player.sell_offer(&offer)?;
+        return Ok(());
+    }
+
+    /// Give up the current offer before it expires. Skipping an auction forfeits the lot, even when
+    /// holding the top bid. Returns OfferExpired like buy, skipping an offer which already expired
+    /// would skip the offer which replaced it
+    pub(crate) fn skip(&mut self, action :&PlayerAction) -> Result<(), GameError> {
+        self.current_offer(action)?;
+        self.offer_controller.take_auction();
+        return Ok(());
+    }
+
+    /// Settle the auction of the offer which just ended, the lot goes to the player if they hold the
+    /// top bid. Returns None if the ended offer was no auction, else the ended offer, its auction and
+    /// whether the player won the lot. An error means the player won but could not take the lot
+    pub(crate) fn settle_auction(&mut self) -> Option<(FruitOffer, Auction, Result<bool, GameError>)> {
+        let offer = self.offer_controller.get_offer()?;
+        let auction = self.offer_controller.take_auction()?;
+        let won = match auction.get_top_bid(){
+            Some((_, bid)) if auction.is_player_top_bidder() => {
+                self.player.buy_lot(offer.get_fruit(), offer.get_quality(), auction.get_lot_size(), bid, offer.get_id(), false)
+                    .map(|_| true)
+            }
+            _ => { Ok(false) }
+        };
+        return Some((offer, auction, won));
+    }
+
+    /// Current offer if the action was meant for it and made before it expired, else OfferExpired
+    fn current_offer(&self, action :&PlayerAction) -> Result<FruitOffer, GameError> {
+        let offer = self.offer_controller.get_offer().ok_or(GameError::OfferExpired)?;
+        offer.validate_action(action)?;
+        return Ok(offer);
+    }
+}
@@ -0,0 +1,60 @@
+use std::fmt;
+use std::io;
+
+use crate::config::args::Args;
+
+/// Everything which can go wrong while playing. All variants except InvalidArgument, InvalidConfig
+/// and Io are the result of a player interaction which could not be performed and are shown in the
+/// event log, InvalidArgument and InvalidConfig mean the game could not start and Io means the
+/// terminal could not be written to or read from and ends the game.
+#[derive(Debug)]
+pub(crate) enum GameError {
+    /// Player does not have enough cash for the purchase or bid
+    InsufficientFunds,
+    /// Player has no fruit of the kind being sold
+    NoInventory,
+    /// Interaction was meant for an offer which is no longer available
+    OfferExpired,
+    /// Player's storage has no room for the fruit being bought
+    StorageFull,
+    /// Player already holds the top bid of the auction
+    AlreadyTopBidder,
+    /// Fruit can not be sold to an auction, auctions only take bids
+    AuctionOnlyBids,
+    /// Player's cash can not hold the money received from a sale
+    CashOverflow,
+    /// Advisor can not be shown in ranked games
+    AdvisorDisabled,
+    /// Command line argument is no option, ends the game before it starts
+    InvalidArgument(String),
+    /// Config file could not be read or holds an invalid setting, ends the game before it starts
+    InvalidConfig(String),
+    /// Reading from or writing to the terminal failed
+    Io(io::Error)
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self{
+            GameError::InsufficientFunds => { write!(f, "not enough money") }
+            GameError::NoInventory => { write!(f, "no such item in inventory") }
+            GameError::OfferExpired => { write!(f, "offer expired") }
+            GameError::StorageFull => { write!(f, "storage is full") }
+            GameError::AlreadyTopBidder => { write!(f, "already holding the top bid") }
+            GameError::AuctionOnlyBids => { write!(f, "auctions only take bids") }
+            GameError::CashOverflow => { write!(f, "too much cash") }
+            GameError::AdvisorDisabled => { write!(f, "the advisor is disabled in ranked games") }
+            GameError::InvalidArgument(arg) => { write!(f, "unknown argument '{}', {}", arg, Args::USAGE) }
+            GameError::InvalidConfig(message) => { write!(f, "invalid config, {}", message) }
+            GameError::Io(error) => { write!(f, "terminal error: {}", error) }
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+impl From<io::Error> for GameError {
+    fn from(error: io::Error) -> GameError {
+        return GameError::Io(error);
+    }
+}
@@ -0,0 +1,430 @@
+use std::fs::File;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, TryRecvError};
+use std::time::{Duration, Instant};
+
+use crate::bot::Strategy;
+use crate::console::output::Output;
+use crate::console::input::PlayerInteractions;
+use crate::console::key_map::KeyMap;
+use crate::player::ledger;
+use crate::fruit::FruitOffer;
+use crate::fruit::fruit_store::FruitStore;
+use crate::fruit::price_history::PriceHistory;
+use crate::game::GameEvent;
+use crate::game::action::{PlayerAction, ShownOffer};
+use crate::game::advisor::Advisor;
+use crate::game::engine::Engine;
+use crate::game::solver::SolverThreadHandler;
+use crate::game::tutorial::Tutorial;
+use crate::game::error::GameError;
+use crate::config::Config;
+
+/// Single threaded game loop playing the offers by the rules of engine::Engine as events arrive
+pub(crate) struct EventLoop {
+    events: Receiver<GameEvent>,
+    shown_offer: ShownOffer,
+    key_map: KeyMap,
+    output: Output,
+    fruit_store: FruitStore,
+    engine: Engine,
+    price_history: PriceHistory,
+    tutorial: Option<Tutorial>,
+    bot: Option<Box<dyn Strategy>>,
+    last_bot_move: Instant,
+    advisor: Advisor,
+    //Whether to search for the optimal score of the game, never in the tutorial
+    solver: bool,
+    //Searching for the optimal score of the game while it is played, if the solver is used
+    solving: Option<SolverThreadHandler>,
+    //True once every offer of the game has been played
+    finished: bool,
+    //Some while the help is shown, true if the help paused the game and closing it should resume
+    help: Option<bool>,
+    running: bool,
+    terminated: bool
+}
+
+impl EventLoop {
+    pub(crate) fn new(events :Receiver<GameEvent>, output :Output, fruit_store :FruitStore, shown_offer :ShownOffer, key_map :KeyMap, tutorial :Option<Tutorial>) -> EventLoop {
+        return EventLoop {
+            events,
+            shown_offer,
+            key_map,
+            output,
+            fruit_store,
+            //Engine, holding fruit store's current offer and the player
+            engine: Engine::new(),
+            price_history: PriceHistory::new(),
+            tutorial,
+            bot: None,
+            last_bot_move: Instant::now(),
+            advisor: Advisor::new(false),
+            solver: false,
+            solving: None,
+            finished: false,
+            help: None,
+            running: true,
+            terminated: false
+        };
+    }
+
+    /// Let the bot play the game in place of the player
+    pub(crate) fn with_bot(mut self, bot :Option<Box<dyn Strategy>>) -> EventLoop {
+        self.bot = bot;
+        return self;
+    }
+
+    /// Search for the optimal score of the game to compare the player's score against at game over
+    pub(crate) fn with_solver(mut self, solver :bool) -> EventLoop {
+        self.solver = solver;
+        return self;
+    }
+
+    /// Use the advisor instead of one which can be shown in any game
+    pub(crate) fn with_advisor(mut self, advisor :Advisor) -> EventLoop {
+        self.advisor = advisor;
+        return self;
+    }
+
+    /// Play the game until the fruit store runs out of offers or the player ends the game.
+    /// Returns an error if the terminal could not be written to
+    pub(crate) fn run(&mut self) -> Result<(), GameError> {
+        if let Some(bot) = &self.bot {
+            self.output.print_bot(bot.get_name())?;
+        }
+        if self.solver && self.tutorial.is_none() {
+            self.solving = Some(SolverThreadHandler::new(self.fruit_store.get_seed()));
+        }
+        self.next_offer()?;
+        while self.running {
+            let event = match self.time_until_deadline() {
+                None => {
+                    self.events.recv().map_err(|_| RecvTimeoutError::Disconnected)
+                }
+                Some(timeout) => {
+                    self.events.recv_timeout(timeout)
+                }
+            };
+            match event {
+                Ok(GameEvent::Key(key, offer_id, at)) => {
+                    if let Some(interaction) = self.key_map.get_interaction(&key) {
+                        self.handle_interaction(PlayerAction { interaction, offer_id, at })?;
+                    }
+                }
+                Ok(GameEvent::Click(column, row, offer_id, at)) => {
+                    if let Some(interaction) = self.output.button_at(column, row) {
+                        self.handle_interaction(PlayerAction { interaction, offer_id, at })?;
+                    }
+                }
+                Ok(GameEvent::Resize) => {
+                    self.output.redraw()?;
+                }
+                Ok(GameEvent::Terminate) => {
+                    self.running = false;
+                    self.terminated = true;
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    self.handle_tick()?;
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.running = false;
+                    self.terminated = true;
+                }
+            }
+        }
+        self.output.print_end(self.engine.get_player(), self.fruit_store.get_seed(), self.advisor.was_used())?;
+        //The score of a game ended early is not compared, dropping the solver cancels its search
+        if let Some(solving) = self.solving.take().filter(|_| self.finished) {
+            self.wait_for_solver(solving)?;
+        }
+        let can_export = !self.key_map.get_keys(&PlayerInteractions::Export).is_empty();
+        if !self.terminated && can_export && !self.engine.get_player().get_ledger().is_empty() {
+            //Game was not ended by a signal so the player is around to answer
+            self.output.print_export_prompt()?;
+            //Only a key answers the prompt, the terminal may be resized while waiting for it
+            loop {
+                match self.events.recv() {
+                    Ok(GameEvent::Key(key, _, _)) => {
+                        if self.key_map.get_interaction(&key) == Some(PlayerInteractions::Export) {
+                            self.export_ledger()?;
+                        }
+                        break;
+                    }
+                    Ok(GameEvent::Resize) => {
+                        self.output.redraw()?;
+                    }
+                    Ok(GameEvent::Click(..)) => {}
+                    Ok(GameEvent::Terminate) | Err(_) => { break; }
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    /// Compare the score against the optimal score once the solver has found it, any key skips the
+    /// comparison instead of waiting for the search to end
+    fn wait_for_solver(&mut self, mut solving :SolverThreadHandler) -> Result<(), GameError> {
+        self.output.print_solving()?;
+        loop {
+            if let Some(optimal) = solving.wait(Duration::from_millis(Config::RENDER_TICK_MILLIS as u64)) {
+                return self.output.print_optimal(self.engine.get_player(), optimal.score);
+            }
+            match self.events.try_recv() {
+                Ok(GameEvent::Key(..)) => { return Ok(()); }
+                Ok(GameEvent::Resize) => {
+                    self.output.redraw()?;
+                }
+                Ok(GameEvent::Click(..)) | Err(TryRecvError::Empty) => {}
+                Ok(GameEvent::Terminate) | Err(TryRecvError::Disconnected) => {
+                    self.terminated = true;
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Write the player's transaction ledger to a CSV file named after the game's seed
+    fn export_ledger(&mut self) -> Result<(), GameError> {
+        let path = Config::ledger_export_path(self.fruit_store.get_seed());
+        let result = File::create(&path)
+            .and_then(|mut file| ledger::write_csv(self.engine.get_player().get_ledger(), &mut file))
+            .map_err(GameError::from);
+        return self.output.print_export(&path, &result);
+    }
+
+    /// Time until the current offer expires or the next render tick, None while paused
+    fn time_until_deadline(&self) -> Option<Duration> {
+        if self.engine.get_offer_controller().is_paused() {
+            return None;
+        }
+        let offer = self.engine.get_offer_controller().get_offer()?;
+        let left = offer.get_duration_ms().saturating_sub(offer.millis_since_offer());
+        return Some(Duration::from_millis(left.min(Config::RENDER_TICK_MILLIS) as u64));
+    }
+
+    /// Replace the current offer once it has expired, else update the timeout bar, let the auction's
+    /// rival act and release held keys
+    fn handle_tick(&mut self) -> Result<(), GameError> {
+        let offer = match self.engine.get_offer_controller().get_offer() {
+            None => { return Ok(()); }
+            Some(offer) => { offer }
+        };
+        let now = Instant::now();
+        let elapsed = offer.millis_since_offer_at(now);
+        if elapsed >= offer.get_duration_ms() {
+            return self.next_offer();
+        }
+
+        let proc = offer.get_duration_as_percent_of_elapsed(elapsed);
+        self.output.print_timeout(proc as u32)?;
+
+        if self.engine.tick(now) {
+            if let Some(auction) = self.engine.get_offer_controller().get_auction() {
+                self.output.print_auction(&offer, auction)?;
+            }
+            self.print_advice()?;
+        }
+        return self.bot_move(&offer);
+    }
+
+    /// Ask the bot what to do with the offer and perform it, if a bot plays and its last move was
+    /// at least Config::BOT_MOVE_MILLIS ago
+    fn bot_move(&mut self, offer :&FruitOffer) -> Result<(), GameError> {
+        if self.last_bot_move.elapsed() < Duration::from_millis(Config::BOT_MOVE_MILLIS as u64) {
+            return Ok(());
+        }
+        let bot = match &mut self.bot {
+            None => { return Ok(()); }
+            Some(bot) => { bot }
+        };
+        self.last_bot_move = Instant::now();
+        return match bot.decide(&self.engine.situation(offer)) {
+            None => { Ok(()) }
+            Some(interaction) => {
+                self.handle_interaction(PlayerAction { interaction, offer_id: Some(offer.get_id()), at: Instant::now() })
+            }
+        };
+    }
+
+    /// Update the advisor's rating of the current offer
+    fn print_advice(&mut self) -> Result<(), GameError> {
+        let advice = match self.engine.get_offer_controller().get_offer() {
+            None => { None }
+            Some(offer) => { self.advisor.advise(&self.engine.situation(&offer), &self.price_history) }
+        };
+        return self.output.print_advice(advice.as_ref());
+    }
+
+    /// Settle the ending offer's auction and start the fruit store's next offer. Ends the game when
+    /// the fruit store has no offers left
+    fn next_offer(&mut self) -> Result<(), GameError> {
+        let ended = self.engine.settle_auction();
+        let fruit_store = &mut self.fruit_store;
+        let next = match &mut self.tutorial {
+            None => {
+                fruit_store.next_offer().map(|offer| (offer, Config::AMOUNT_OF_OFFERS - offer.get_id() - 1, None))
+            }
+            Some(tutorial) => {
+                let steps_left = tutorial.steps_left();
+                tutorial.next_step().map(|step| {
+                    let offer = fruit_store.scripted_offer(step.fruit, step.quality, step.base_price);
+                    (offer, steps_left, Some((step.name, step.interaction.clone())))
+                })
+            }
+        };
+        match next {
+            None => {
+                self.running = false;
+                self.finished = true;
+                if let Some((ended_offer, auction, won)) = ended {
+                    self.output.print_player(self.engine.get_player())?;
+                    self.output.print_auction_result(&ended_offer.get_fruit(), &auction, &won)?;
+                }
+            }
+            Some((offer, offers_left, tutorial_prompt)) => {
+                let spoiled = self.engine.start_offer(&offer);
+                self.price_history.record(&offer);
+                self.output.print_offer(&offer, &offers_left)?;
+                self.shown_offer.set(offer.get_id());
+                self.output.print_price_history(&self.price_history)?;
+                self.output.print_player(self.engine.get_player())?;
+                if !spoiled.is_empty() {
+                    self.output.print_spoiled(&spoiled)?;
+                }
+                if let Some((ended_offer, auction, won)) = ended {
+                    self.output.print_auction_result(&ended_offer.get_fruit(), &auction, &won)?;
+                }
+                if let Some((step, interaction)) = tutorial_prompt {
+                    self.output.print_tutorial(step, &interaction, &offer)?;
+                }
+                self.print_advice()?;
+            }
+        }
+        return Ok(());
+    }
+
+    /// Perform the player's interaction on the current offer. Interactions which can not be
+    /// performed are reported on the player feedback line, only terminal errors are returned
+    /// During the tutorial, an interaction the tutorial asked for completes the tutorial's step and
+    /// moves on to the next scripted offer
+    fn handle_interaction(&mut self, action :PlayerAction) -> Result<(), GameError> {
+        let interaction = action.interaction.clone();
+        //Skipping moves on to the next offer by itself, completing the step once the skip succeeded
+        let skipping = interaction == PlayerInteractions::NextOffer && !self.engine.get_offer_controller().is_paused();
+        let result = self.perform_interaction(action);
+        match result{
+            Err(GameError::Io(error)) => { Err(GameError::Io(error)) }
+            Err(error) => { self.output.print_error(&error) }
+            Ok(()) => {
+                if !skipping && self.complete_tutorial_step(&interaction) && self.running {
+                    return self.next_offer();
+                }
+                self.print_advice()
+            }
+        }
+    }
+
+    /// Completes the tutorial's current step if it asked for the interaction, returns whether it did
+    fn complete_tutorial_step(&mut self, interaction :&PlayerInteractions) -> bool {
+        return match &mut self.tutorial {
+            Some(tutorial) => { tutorial.complete(interaction) }
+            None => { false }
+        };
+    }
+
+    fn perform_interaction(&mut self, action :PlayerAction) -> Result<(), GameError> {
+        if self.engine.get_offer_controller().is_paused() {
+            //While paused only resuming, the help or ending the game is possible
+            match action.interaction{
+                PlayerInteractions::Pause => {
+                    if self.help.take().is_some() {
+                        self.output.print_help(false)?;
+                    }
+                    self.engine.get_offer_controller_mut().resume();
+                    self.output.print_paused(false)?;
+                }
+                PlayerInteractions::Help => {
+                    match self.help.take() {
+                        Some(resume) => {
+                            self.output.print_help(false)?;
+                            if resume {
+                                self.engine.get_offer_controller_mut().resume();
+                            }
+                        }
+                        None => {
+                            self.help = Some(false);
+                            self.output.print_help(true)?;
+                        }
+                    }
+                }
+                PlayerInteractions::Exit => {
+                    self.running = false;
+                }
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        let offer = match self.engine.get_offer_controller().get_offer() {
+            None => { return Ok(()); }
+            Some(offer) => { offer }
+        };
+
+        let interaction = match self.engine.filter_held(&action, offer.get_id()) {
+            None => { return Ok(()); }
+            Some(interaction) => { interaction }
+        };
+        match interaction{
+            PlayerInteractions::Buy => {
+                self.engine.buy(&action)?;
+                let offer_controller = self.engine.get_offer_controller();
+                match offer_controller.get_auction(){
+                    Some(auction) => {
+                        self.output.print_auction(&offer, auction)?;
+                    }
+                    None => {
+                        self.output.print_units_bought(&offer, offer_controller.get_units_bought())?;
+                        self.output.print_player(self.engine.get_player())?;
+                    }
+                }
+            }
+            PlayerInteractions::Sell => {
+                self.engine.sell(&action)?;
+                self.output.print_player(self.engine.get_player())?;
+            }
+            PlayerInteractions::Exit => {
+                self.running = false;
+            }
+            PlayerInteractions::NextOffer => {
+                self.engine.skip(&action)?;
+                self.complete_tutorial_step(&PlayerInteractions::NextOffer);
+                self.output.print_skipping_turn()?;
+                self.next_offer()?;
+            }
+            PlayerInteractions::Pause => {
+                self.engine.get_offer_controller_mut().pause();
+                self.output.print_paused(true)?;
+            }
+            PlayerInteractions::Help => {
+                //The game is paused while the help is shown so the offer does not expire unseen
+                self.engine.get_offer_controller_mut().pause();
+                self.help = Some(true);
+                self.output.print_help(true)?;
+            }
+            PlayerInteractions::Export => {
+                self.export_ledger()?;
+            }
+            PlayerInteractions::Charts => {
+                self.output.toggle_charts()?;
+            }
+            PlayerInteractions::Advisor => {
+                self.advisor.toggle()?;
+            }
+            PlayerInteractions::Info => {
+                let range = FruitStore::range_for_fruit(&offer.get_fruit(), &offer.get_season(), &offer.get_event());
+                self.output.print_info(&offer.get_fruit(),range)?;
+            }
+        }
+        return Ok(());
+    }
+}
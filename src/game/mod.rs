@@ -0,0 +1,30 @@
+pub mod action;
+pub mod advisor;
+pub mod engine;
+pub mod error;
+pub mod event_loop;
+pub mod money;
+pub mod signals;
+pub mod simulation;
+pub mod solver;
+pub mod tournament;
+pub mod tutorial;
+
+use std::time::Instant;
+
+use termion::event::Key;
+
+/// Events the game loop reacts to besides the passing of time
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum GameEvent {
+    /// Key pressed by the player, with the id of the offer shown when the key was pressed and
+    /// when it was pressed, see action::PlayerAction
+    Key(Key, Option<u32>, Instant),
+    /// Left mouse button pressed at the column and row of the screen, counted from 1, with the id
+    /// of the offer shown and when it was pressed like keys
+    Click(u16, u16, Option<u32>, Instant),
+    /// Terminal was resized and the screen has to be laid out again
+    Resize,
+    /// Game was asked to end by a signal such as SIGTERM or SIGHUP
+    Terminate
+}
@@ -0,0 +1,116 @@
+use std::fmt;
+
+/// Amount of money in fixed-point cents, used for the player's cash and every price in the game.
+/// Arithmetic which could overflow is checked, callers decide what an overflow means.
+/// Money is written with two decimals and without a currency, such as 4.50, how it is shown to the
+/// player is up to the locale, see Locale::money
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub(crate) struct Money {
+    cents: u64
+}
+
+impl Money {
+    pub(crate) const fn from_cents(cents :u64) -> Money {
+        return Money { cents };
+    }
+
+    pub(crate) const fn from_dollars(dollars :u64) -> Money {
+        return Money { cents: dollars * 100 };
+    }
+
+    pub(crate) fn get_cents(&self) -> u64 {
+        return self.cents;
+    }
+
+    /// Amount as a number of dollars, for averages and prices per fruit which need not be whole cents
+    pub(crate) fn as_dollars(&self) -> f64 {
+        return self.cents as f64 / 100.0;
+    }
+
+    /// Returns true if the amount has no cents
+    pub(crate) fn is_whole(&self) -> bool {
+        return self.cents % 100 == 0;
+    }
+
+    /// Returns None if the sum does not fit
+    pub(crate) fn checked_add(&self, other :Money) -> Option<Money> {
+        return self.cents.checked_add(other.cents).map(Money::from_cents);
+    }
+
+    /// Returns None if other is more than the amount
+    pub(crate) fn checked_sub(&self, other :Money) -> Option<Money> {
+        return self.cents.checked_sub(other.cents).map(Money::from_cents);
+    }
+
+    /// Amount times the quantity, the largest amount of money if the product does not fit
+    pub(crate) fn saturating_mul(&self, quantity :u32) -> Money {
+        return Money::from_cents(self.cents.saturating_mul(quantity as u64));
+    }
+
+    /// Share of one of units in the amount rounded down to whole cents, such as the price of one
+    /// fruit in a lot. The amount itself when units is 0
+    pub(crate) fn per_unit(&self, units :u32) -> Money {
+        return Money::from_cents(self.cents / units.max(1) as u64);
+    }
+
+    /// Percentage of the amount rounded down to whole cents, the amount itself at 100 percent.
+    /// Saturates instead of overflowing
+    pub(crate) fn percent(&self, percent :u32) -> Money {
+        return Money::from_cents(self.cents.saturating_mul(percent as u64) / 100);
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}.{:02}", self.cents / 100, self.cents % 100);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn written_with_two_decimals() {
+        assert_eq!(Money::from_cents(0).to_string(), "0.00");
+        assert_eq!(Money::from_cents(5).to_string(), "0.05");
+        assert_eq!(Money::from_cents(450).to_string(), "4.50");
+        assert_eq!(Money::from_dollars(12).to_string(), "12.00");
+        assert_eq!(Money::from_cents(u64::MAX).to_string(), "184467440737095516.15");
+    }
+
+    #[test]
+    fn whole_amounts_and_dollars() {
+        assert!(Money::from_dollars(3).is_whole());
+        assert!(!Money::from_cents(301).is_whole());
+        assert_eq!(Money::from_cents(250).as_dollars(), 2.5);
+        assert_eq!(Money::from_dollars(7).get_cents(), 700);
+    }
+
+    #[test]
+    fn percent_rounds_down_to_whole_cents() {
+        assert_eq!(Money::from_cents(199).percent(50), Money::from_cents(99));
+        assert_eq!(Money::from_cents(333).percent(100), Money::from_cents(333));
+        assert_eq!(Money::from_cents(1).percent(99), Money::from_cents(0));
+        assert_eq!(Money::from_cents(250).percent(160), Money::from_cents(400));
+    }
+
+    #[test]
+    fn per_unit_rounds_down_to_whole_cents() {
+        assert_eq!(Money::from_dollars(10).per_unit(3), Money::from_cents(333));
+        assert_eq!(Money::from_cents(7).per_unit(0), Money::from_cents(7));
+        assert_eq!(Money::from_cents(7).per_unit(8), Money::from_cents(0));
+    }
+
+    #[test]
+    fn overflow_is_checked_or_saturates() {
+        let most = Money::from_cents(u64::MAX);
+        assert_eq!(most.checked_add(Money::from_cents(1)), None);
+        assert_eq!(Money::from_cents(1).checked_add(Money::from_cents(2)), Some(Money::from_cents(3)));
+        assert_eq!(Money::from_cents(1).checked_sub(Money::from_cents(2)), None);
+        assert_eq!(Money::from_cents(5).checked_sub(Money::from_cents(5)), Some(Money::from_cents(0)));
+        assert_eq!(most.saturating_mul(2), most);
+        assert_eq!(Money::from_cents(3).saturating_mul(4), Money::from_cents(12));
+        assert_eq!(most.percent(150), Money::from_cents(u64::MAX / 100));
+    }
+}
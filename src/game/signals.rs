@@ -0,0 +1,40 @@
+use std::thread;
+use std::thread::JoinHandle;
+use std::sync::mpsc;
+use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM, SIGWINCH};
+use signal_hook::iterator::{Handle, Signals};
+
+use crate::game::GameEvent;
+
+/// Signal handler containing thread which turns SIGTERM, SIGHUP and SIGINT into a
+/// GameEvent::Terminate so the game loop can end the game gracefully, and SIGWINCH into a
+/// GameEvent::Resize so the screen is laid out again for the new terminal size.
+pub(crate) struct SignalThreadHandler {
+    handle: Handle,
+    thread: Option<JoinHandle<()>>
+}
+
+impl SignalThreadHandler {
+    /// Start a new thread listening for signals. Stop must be called for the thread to end.
+    pub(crate) fn new(send_event :mpsc::Sender<GameEvent>) -> std::io::Result<SignalThreadHandler> {
+        let mut signals = Signals::new([SIGTERM, SIGHUP, SIGINT, SIGWINCH])?;
+        let handle = signals.handle();
+        let thread = thread::spawn(move || {
+            for signal in signals.forever() {
+                let event = if signal == SIGWINCH { GameEvent::Resize } else { GameEvent::Terminate };
+                if send_event.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+        return Ok(SignalThreadHandler { handle, thread: Some(thread) });
+    }
+
+    /// Stops signal thread and waits for it to end
+    pub(crate) fn stop(&mut self) {
+        self.handle.close();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+use rand::{thread_rng, Rng};
+
+use crate::bot::{self, Strategy};
+use crate::console::input::PlayerInteractions;
+use crate::config::Config;
+use crate::fruit::fruit_store::FruitStore;
+use crate::game::action::PlayerAction;
+use crate::game::engine::Engine;
+use crate::game::error::GameError;
+use crate::game::money::Money;
+
+/// Standard normal quantile of a two sided 95 percent confidence interval
+const Z_95: f64 = 1.96;
+
+/// Plays games with the bot named bot_name without a terminal, one game for each seed counting up
+/// from seed, a random seed when None. Returns the summary of the scores, InvalidArgument if there
+/// is no bot with the name
+pub(crate) fn simulate(bot_name :&str, games :u32, seed :Option<u64>) -> Result<ScoreSummary, GameError> {
+    let first_seed = seed.unwrap_or_else(|| thread_rng().gen());
+    return Ok(ScoreSummary::new(bot_name, first_seed, &scores(bot_name, games, first_seed)?));
+}
+
+/// Scores of games played with the bot named bot_name, one game for each seed counting up from
+/// first_seed. The bot is created anew for every game. Returns InvalidArgument if there is no bot
+/// with the name
+pub(crate) fn scores(bot_name :&str, games :u32, first_seed :u64) -> Result<Vec<Money>, GameError> {
+    let mut scores = Vec::with_capacity(games as usize);
+    for game in 0..games {
+        let seed = first_seed.wrapping_add(game as u64);
+        let mut strategy = bot::by_name(bot_name, seed)?;
+        scores.push(play(strategy.as_mut(), seed));
+    }
+    return Ok(scores);
+}
+
+/// Config settings which shape the score, reported with the scores so reports of different
+/// configs can be told apart
+pub(crate) fn describe_config() -> String {
+    return format!("starting cash {}, {} offers, storage {}, offers last {} to {} ms, bot moves every {} ms",
+                   Config::STARTING_CASH, Config::AMOUNT_OF_OFFERS, Config::STORAGE_CAPACITY,
+                   Config::OFFER_DURATION_MIN_MILLIS, Config::OFFER_DURATION_MAX_MILLIS, Config::BOT_MOVE_MILLIS);
+}
+
+/// Play the game of the seed with the strategy and return the score, the cash left at game over.
+/// The game is played by the game loop's rules, see engine::Engine, on a fake clock which is
+/// counted instead of waited for. Like the game loop the clock ticks every Config::RENDER_TICK_MILLIS
+/// and when the offer expires, letting the auction's rival act, and the strategy moves on ticks
+/// every Config::BOT_MOVE_MILLIS like a bot does in the terminal. Moves which can not be performed,
+/// such as buying without the cash, are skipped as they are when a bot plays
+pub(crate) fn play(strategy :&mut dyn Strategy, seed :u64) -> Money {
+    let mut fruit_store = FruitStore::with_seed(seed);
+    let mut engine = Engine::new();
+    let tick = Duration::from_millis(Config::RENDER_TICK_MILLIS as u64);
+    let bot_move = Duration::from_millis(Config::BOT_MOVE_MILLIS as u64);
+    let mut now = Instant::now();
+    let mut last_move = now;
+    while let Some(offer) = fruit_store.next_offer_at(now) {
+        engine.start_offer(&offer);
+        loop {
+            now += offer.expires_at().saturating_duration_since(now).min(tick);
+            if now >= offer.expires_at() {
+                break;
+            }
+            engine.tick(now);
+            if now.duration_since(last_move) < bot_move {
+                continue;
+            }
+            last_move = now;
+            let interaction = match strategy.decide(&engine.situation(&offer)) {
+                None => { continue; }
+                Some(interaction) => { interaction }
+            };
+            let action = PlayerAction { interaction, offer_id: Some(offer.get_id()), at: now };
+            match engine.filter_held(&action, offer.get_id()) {
+                Some(PlayerInteractions::Buy) => { let _ = engine.buy(&action); }
+                Some(PlayerInteractions::Sell) => { let _ = engine.sell(&action); }
+                //The next offer starts right away, as it does in the game loop
+                Some(PlayerInteractions::NextOffer) if engine.skip(&action).is_ok() => { break; }
+                _ => {}
+            }
+        }
+        engine.settle_auction();
+    }
+    return engine.get_player().get_cash();
+}
+
+/// Distribution of the scores of simulated games. Games ending with less cash than the player
+/// started with are losses, games ending with less cash than Config::MIN_PRICE, the price of the
+/// cheapest fruit, are bankruptcies. Mean and standard deviation are in dollars
+pub(crate) struct ScoreSummary {
+    pub(crate) bot_name: String,
+    pub(crate) first_seed: u64,
+    pub(crate) games: usize,
+    pub(crate) mean: f64,
+    pub(crate) std_dev: f64,
+    pub(crate) min: Money,
+    pub(crate) p10: Money,
+    pub(crate) p25: Money,
+    pub(crate) median: Money,
+    pub(crate) p75: Money,
+    pub(crate) p90: Money,
+    pub(crate) max: Money,
+    pub(crate) losses: usize,
+    pub(crate) bankruptcies: usize
+}
+
+impl ScoreSummary {
+    pub(crate) fn new(bot_name :&str, first_seed :u64, scores :&[Money]) -> ScoreSummary {
+        let mut sorted = scores.to_vec();
+        sorted.sort();
+        let games = sorted.len().max(1) as f64;
+        let mean = sorted.iter().map(|score| score.as_dollars()).sum::<f64>() / games;
+        //Sample standard deviation, 0 for a single game
+        let squares = sorted.iter().map(|score| (score.as_dollars() - mean).powi(2)).sum::<f64>();
+        let std_dev = (squares / (games - 1.0).max(1.0)).sqrt();
+        return ScoreSummary {
+            bot_name: String::from(bot_name),
+            first_seed,
+            games: sorted.len(),
+            mean,
+            std_dev,
+            min: ScoreSummary::percentile(&sorted, 0),
+            p10: ScoreSummary::percentile(&sorted, 10),
+            p25: ScoreSummary::percentile(&sorted, 25),
+            median: ScoreSummary::percentile(&sorted, 50),
+            p75: ScoreSummary::percentile(&sorted, 75),
+            p90: ScoreSummary::percentile(&sorted, 90),
+            max: ScoreSummary::percentile(&sorted, 100),
+            losses: sorted.iter().filter(|score| **score < Config::STARTING_CASH).count(),
+            bankruptcies: sorted.iter().filter(|score| **score < Config::MIN_PRICE).count()
+        };
+    }
+
+    /// Score which percent of the sorted scores are at or below, by nearest rank. No money when
+    /// there are no scores
+    fn percentile(sorted :&[Money], percent :usize) -> Money {
+        if sorted.is_empty() {
+            return Money::from_cents(0);
+        }
+        let rank = (percent * sorted.len()).div_ceil(100);
+        return sorted[rank.clamp(1, sorted.len()) - 1];
+    }
+
+    /// Range which holds the mean score of the bot with 95 percent confidence, from the normal
+    /// approximation of the mean of the games played
+    pub(crate) fn confidence_interval(&self) -> (f64, f64) {
+        let margin = Z_95 * self.std_dev / (self.games.max(1) as f64).sqrt();
+        return (self.mean - margin, self.mean + margin);
+    }
+
+    /// Share of the games in percent
+    pub(crate) fn share(&self, count :usize) -> f64 {
+        return count as f64 * 100.0 / self.games.max(1) as f64;
+    }
+}
+
+/// Report of the simulation, leading with the Config settings which shape the score so reports of
+/// different configs can be compared
+impl fmt::Display for ScoreSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} games played by the {} bot, seeds {} to {}",
+                 self.games, self.bot_name, self.first_seed,
+                 self.first_seed.wrapping_add(self.games.saturating_sub(1) as u64))?;
+        writeln!(f, "config: {}", describe_config())?;
+        let (low, high) = self.confidence_interval();
+        writeln!(f, "score: mean {:.2} (95% CI {:.2} to {:.2}), min {}, p10 {}, p25 {}, median {}, p75 {}, p90 {}, max {}",
+                 self.mean, low, high, self.min, self.p10, self.p25, self.median, self.p75, self.p90, self.max)?;
+        return write!(f, "losses: {} ({:.1}%), bankruptcies: {} ({:.1}%)",
+                      self.losses, self.share(self.losses), self.bankruptcies, self.share(self.bankruptcies));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::midpoint::Midpoint;
+
+    fn dollars(amounts :&[u64]) -> Vec<Money> {
+        return amounts.iter().map(|amount| Money::from_dollars(*amount)).collect();
+    }
+
+    #[test]
+    fn percentiles_take_the_nearest_rank() {
+        let summary = ScoreSummary::new("test", 1, &dollars(&[100, 10, 90, 20, 80, 30, 70, 40, 60, 50]));
+        assert_eq!(summary.min, Money::from_dollars(10));
+        assert_eq!(summary.p10, Money::from_dollars(10));
+        assert_eq!(summary.p25, Money::from_dollars(30));
+        assert_eq!(summary.median, Money::from_dollars(50));
+        assert_eq!(summary.p75, Money::from_dollars(80));
+        assert_eq!(summary.p90, Money::from_dollars(90));
+        assert_eq!(summary.max, Money::from_dollars(100));
+        assert_eq!(summary.mean, 55.0);
+    }
+
+    #[test]
+    fn losses_and_bankruptcies_are_counted() {
+        let scores = vec![Money::from_cents(0), Config::MIN_PRICE, Config::STARTING_CASH, Money::from_dollars(1)];
+        let summary = ScoreSummary::new("test", 1, &scores);
+        assert_eq!(summary.losses, 3);
+        assert_eq!(summary.bankruptcies, 1);
+        assert_eq!(summary.share(summary.losses), 75.0);
+    }
+
+    #[test]
+    fn confidence_interval_narrows_with_more_games() {
+        let summary = ScoreSummary::new("test", 1, &dollars(&[40, 60]));
+        assert_eq!(summary.mean, 50.0);
+        //Sample standard deviation of 40 and 60 is the square root of 200
+        assert!((summary.std_dev - 200f64.sqrt()).abs() < 1e-9);
+        let (low, high) = summary.confidence_interval();
+        assert!((high - low - 2.0 * Z_95 * 10.0).abs() < 1e-9);
+        let more_games = ScoreSummary::new("test", 1, &dollars(&[40, 60, 40, 60, 40, 60, 40, 60]));
+        let (more_low, more_high) = more_games.confidence_interval();
+        assert!(more_high - more_low < high - low);
+    }
+
+    #[test]
+    fn single_and_no_games_have_no_spread() {
+        let single = ScoreSummary::new("test", 1, &dollars(&[70]));
+        assert_eq!(single.std_dev, 0.0);
+        assert_eq!(single.confidence_interval(), (70.0, 70.0));
+        let none = ScoreSummary::new("test", 1, &[]);
+        assert_eq!(none.games, 0);
+        assert_eq!(none.median, Money::from_cents(0));
+        assert_eq!(none.confidence_interval(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn games_replay_the_same_for_a_seed() {
+        for seed in 0..5 {
+            assert_eq!(play(&mut Midpoint::new(), seed), play(&mut Midpoint::new(), seed));
+        }
+    }
+}
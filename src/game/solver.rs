@@ -0,0 +1,550 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt;
+use std::iter;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use strum::IntoEnumIterator;
+
+use crate::config::Config;
+use crate::fruit::{Fruit, FruitOffer, OfferKind};
+use crate::fruit::auction::Auction;
+use crate::fruit::fruit_store::FruitStore;
+use crate::fruit::quality::Quality;
+use crate::game::money::Money;
+
+/// Fruit of one kind and grade which spoils on the same offer
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct Stock {
+    fruit: Fruit,
+    quality: Quality,
+    spoils_at: u32,
+    count: u32
+}
+
+/// Fruit sold and bought on an offer. Sold fruit was held before the offer and is sold first, best
+/// grade first as the game does. Fruit is then bought one lot at a time, and after each lot the
+/// fruit still to be sold back is sold back to the offer, up to the lot's size
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct Trade {
+    sold: u32,
+    bought: u32,
+    sold_back: u32
+}
+
+/// Cash after an offer, reached from the state at parent among the states of the previous offer by
+/// the trade on the offer
+struct Step {
+    cash: Money,
+    parent: usize,
+    trade: Trade
+}
+
+/// Fruit sold and bought on an offer by the solver's plan, and the cash left afterwards. Fruit sold
+/// back was bought on the offer, see solve_offers
+pub(crate) struct Move {
+    pub(crate) offer_id: u32,
+    pub(crate) fruit: Fruit,
+    pub(crate) sold: u32,
+    pub(crate) bought: u32,
+    pub(crate) sold_back: u32,
+    pub(crate) cash: Money
+}
+
+/// Plan ending with the most cash for the game played with seed, and its score
+pub(crate) struct Solution {
+    pub(crate) seed: u64,
+    pub(crate) score: Money,
+    pub(crate) moves: Vec<Move>
+}
+
+/// Searches for the optimal score of the game played with seed, knowing every offer in advance, see
+/// solve_offers
+pub(crate) fn solve(seed :u64) -> Solution {
+    return solve_offers(seed, &offers_for_seed(seed), &AtomicBool::new(false)).expect("solver is never canceled");
+}
+
+/// Every offer of the game played with seed
+fn offers_for_seed(seed :u64) -> Vec<FruitOffer> {
+    let mut fruit_store = FruitStore::with_seed(seed);
+    return iter::from_fn(|| fruit_store.next_offer()).collect();
+}
+
+/// Searches for the plan of purchases and sales on the offers ending with the most cash, offers
+/// being played out without running out of time. The state after an offer is the inventory held
+/// and the most cash any plan holds with it, every state the game's rules can reach is searched
+/// except for states which can not end with more cash than another state:
+/// - Of two states with the same inventory the one with less cash.
+/// - Of two states where one holds the fruit of the other and more, the one holding more if its cash
+///   and what its fruit can still be sold for does not add up to more than for the other. The
+///   other can make the same trades, skipping sales of the fruit it does not hold.
+///
+/// On each offer every amount of the offered fruit can be sold, in the order the game sells in,
+/// and then every amount bought which fits in cash and storage. Buying a volume discounted fruit
+/// and selling it back counts towards the discount, bulk lots can be sold back in part. Auctions
+/// are won with the lowest winning bid, by outbidding the rival right before the offer expires.
+/// The states kept grow quickly with the offers, so a whole game takes long to solve. Returns None
+/// if canceled before the search ended, cancel is checked before the trades of each state
+fn solve_offers(seed :u64, offers :&[FruitOffer], cancel :&AtomicBool) -> Option<Solution> {
+    let sale_prices = SalePrices::new(offers);
+    let mut inventories: Vec<Vec<Stock>> = vec![Vec::new()];
+    let mut layers: Vec<Vec<Step>> = vec![vec![Step { cash: Config::STARTING_CASH, parent: 0, trade: Trade::default() }]];
+    for offer in offers {
+        let mut reached: HashMap<Vec<Stock>, Step> = HashMap::new();
+        for (parent, (inventory, step)) in inventories.iter().zip(layers[layers.len() - 1].iter()).enumerate() {
+            if cancel.load(Ordering::Relaxed) {
+                return None;
+            }
+            for (next, cash, trade) in successors(offer, inventory, step.cash, &sale_prices) {
+                if reached.get(&next).is_none_or(|reached| reached.cash < cash) {
+                    reached.insert(next, Step { cash, parent, trade });
+                }
+            }
+        }
+        let (next_inventories, steps) = undominated(reached, |inventory, cash| sale_prices.worth(inventory, cash, offer.get_id()));
+        inventories = next_inventories;
+        layers.push(steps);
+    }
+
+    //Fruit left at game over is worth nothing, the best plan ends with the most cash
+    let (mut index, best) = layers[layers.len() - 1].iter().enumerate().max_by_key(|(_, step)| step.cash)?;
+    let score = best.cash;
+    let mut moves = Vec::new();
+    for (offer, layer) in offers.iter().zip(layers.iter().skip(1)).rev() {
+        let step = &layer[index];
+        if step.trade != Trade::default() {
+            let Trade { sold, bought, sold_back } = step.trade;
+            moves.push(Move { offer_id: offer.get_id(), fruit: offer.get_fruit(), sold, bought, sold_back, cash: step.cash });
+        }
+        index = step.parent;
+    }
+    moves.reverse();
+    return Some(Solution { seed, score, moves });
+}
+
+/// States reached on the offer from holding the inventory and cash, see solve_offers for the trades
+/// tried. Trades which are sure to end with less than another trade are left out
+fn successors(offer :&FruitOffer, inventory :&[Stock], cash :Money, sale_prices :&SalePrices) -> Vec<(Vec<Stock>, Money, Trade)> {
+    let (fruit, quality) = (offer.get_fruit(), offer.get_quality());
+    let mut inventory: Vec<Stock> = inventory.iter().filter(|stock| stock.spoils_at > offer.get_id()).copied().collect();
+    let bought = Stock { fruit, quality, spoils_at: offer.get_id() + Config::shelf_life_for_quality(&quality), count: 0 };
+    let worth = sale_prices.best_sale(&bought, offer.get_id());
+    let mut successors = Vec::new();
+
+    //Fruit can not be sold to auctions, the lot is won or left to the rival
+    if let Some(auction) = Auction::new(offer) {
+        successors.push((inventory.clone(), cash, Trade::default()));
+        let bid = auction.get_lowest_winning_bid();
+        let lot = auction.get_lot_size();
+        if held(&inventory) + lot <= Config::STORAGE_CAPACITY && worth.saturating_mul(lot) > bid {
+            if let Some(left) = cash.checked_sub(bid) {
+                add_stock(&mut inventory, Stock { count: lot, ..bought });
+                successors.push((inventory, left, Trade { sold: 0, bought: lot, sold_back: 0 }));
+            }
+        }
+        return successors;
+    }
+
+    //Fruit of better or the same grade is sold before fruit bought on the offer, fruit bought on the
+    //offer can only be sold back once all of it is sold
+    let ahead: u32 = inventory.iter().filter(|stock| stock.fruit == fruit && stock.quality <= quality).map(|stock| stock.count).sum();
+    let resale_base_price = offer.get_resale_base_price();
+    let (mut cash, mut sold) = (cash, 0);
+    loop {
+        let next = inventory.iter().position(|stock| stock.fruit == fruit);
+        let price = next.map(|index| inventory[index].quality.sell_price(resale_base_price));
+        //Keeping fruit which sells for less later than now ends with less than selling it
+        let keep = match (next, price) {
+            (Some(index), Some(price)) => { price < sale_prices.best_sale(&inventory[index], offer.get_id()) }
+            _ => { true }
+        };
+        if keep {
+            for (count, left, trade) in purchases(offer, held(&inventory), cash, sold >= ahead, worth) {
+                let mut next = inventory.clone();
+                if count > 0 {
+                    add_stock(&mut next, Stock { count, ..bought });
+                }
+                successors.push((next, left, Trade { sold, ..trade }));
+            }
+        }
+        let (index, price) = match (next, price) {
+            (Some(index), Some(price)) => { (index, price) }
+            _ => { break; }
+        };
+        cash = match cash.checked_add(price) {
+            None => { break; }
+            Some(cash) => { cash }
+        };
+        inventory[index].count -= 1;
+        if inventory[index].count == 0 {
+            inventory.remove(index);
+        }
+        sold += 1;
+    }
+    return successors;
+}
+
+/// Amounts of fruit which can be bought from the offer holding held fruit and cash, with the cash
+/// left and the trade buying them for the most cash, sold fruit left out. Fruit bought from the
+/// offer is only sold back if can_sell_back, and never bought if it does not sell for more than
+/// it costs
+fn purchases(offer :&FruitOffer, held :u32, cash :Money, can_sell_back :bool, worth :Money) -> Vec<(u32, Money, Trade)> {
+    let mut purchases = vec![(0, cash, Trade::default())];
+    let room = Config::STORAGE_CAPACITY.saturating_sub(held);
+    let resale = offer.get_quality().sell_price(offer.get_resale_base_price());
+    match offer.get_kind() {
+        OfferKind::Bulk(lot) => {
+            let price = offer.get_price();
+            if worth.saturating_mul(lot.lot_size) <= price || lot.lot_size > room || price > cash {
+                return purchases;
+            }
+            //After the first lot its unwanted fruit is sold back, making room for the next lots
+            let sold_backs = if can_sell_back { lot.lot_size } else { 1 };
+            for lots in 1..=room / lot.lot_size + 1 {
+                for sold_back in 0..sold_backs {
+                    let count = (lots * lot.lot_size).saturating_sub(sold_back);
+                    let left = cash.checked_add(resale.saturating_mul(sold_back))
+                        .and_then(|left| left.checked_sub(price.saturating_mul(lots)));
+                    if let (true, Some(left)) = (count <= room && count > 0, left) {
+                        purchases.push((count, left, Trade { sold: 0, bought: lots * lot.lot_size, sold_back }));
+                    }
+                }
+            }
+        }
+        _ => {
+            //Fruit bought before the volume discount applies can be sold back to reach the discount
+            if worth <= offer.get_price_after(u32::MAX) {
+                return purchases;
+            }
+            let discount_after = match offer.get_kind() {
+                OfferKind::Tiered(tiers) if can_sell_back && room > 0 => { tiers.threshold }
+                _ => { 0 }
+            };
+            let mut best: Vec<Option<(Money, Trade)>> = vec![None; room as usize + 1];
+            let mut cycled = Some(cash);
+            for sold_back in 0..=discount_after {
+                let mut left = match cycled {
+                    None => { break; }
+                    Some(left) => { left }
+                };
+                for count in 1..=room {
+                    left = match left.checked_sub(offer.get_price_after(sold_back + count - 1)) {
+                        None => { break; }
+                        Some(left) => { left }
+                    };
+                    if best[count as usize].is_none_or(|(cash, _)| cash < left) {
+                        best[count as usize] = Some((left, Trade { sold: 0, bought: count + sold_back, sold_back }));
+                    }
+                }
+                let price = offer.get_price_after(sold_back);
+                cycled = cycled.and_then(|cycled| cycled.checked_sub(price)).and_then(|cycled| cycled.checked_add(resale));
+            }
+            purchases.extend(best.iter().enumerate().filter_map(|(count, best)| best.map(|(left, trade)| (count as u32, left, trade))));
+        }
+    }
+    return purchases;
+}
+
+/// Amount of fruit in the inventory
+fn held(inventory :&[Stock]) -> u32 {
+    return inventory.iter().map(|stock| stock.count).sum();
+}
+
+/// States of the offer which are not sure to end with less cash than another one, see
+/// solve_offers. worth is the cash of a state and what its fruit can still be sold for
+fn undominated(reached :HashMap<Vec<Stock>, Step>, worth :impl Fn(&[Stock], Money) -> Money) -> (Vec<Vec<Stock>>, Vec<Step>) {
+    let mut states: Vec<(Vec<Stock>, Step)> = reached.into_iter().collect();
+    states.sort_by_key(|(inventory, step)| (held(inventory), Reverse(step.cash)));
+    //Most any state holding the inventory or less of it is worth, a state worth no more is left out
+    let mut best_within: HashMap<Vec<Stock>, Money> = HashMap::with_capacity(states.len());
+    let (mut inventories, mut steps) = (Vec::new(), Vec::new());
+    for (inventory, step) in states {
+        let own = worth(&inventory, step.cash);
+        let mut best = None;
+        for index in 0..inventory.len() {
+            let mut less = inventory.clone();
+            less[index].count -= 1;
+            if less[index].count == 0 {
+                less.remove(index);
+            }
+            if let Some(&within) = best_within.get(&less) {
+                best = best.max(Some(within));
+            }
+        }
+        match best {
+            Some(best) if best >= own => {
+                best_within.insert(inventory, best);
+            }
+            _ => {
+                best_within.insert(inventory.clone(), own);
+                inventories.push(inventory);
+                steps.push(step);
+            }
+        }
+    }
+    return (inventories, steps);
+}
+
+/// Add the stock to the inventory, keeping the inventory in the order the game sells fruit in
+fn add_stock(inventory :&mut Vec<Stock>, stock :Stock) {
+    let key = |stock :&Stock| (stock.fruit as usize, stock.quality, stock.spoils_at);
+    match inventory.binary_search_by_key(&key(&stock), key) {
+        Ok(index) => { inventory[index].count += stock.count; }
+        Err(index) => { inventory.insert(index, stock); }
+    }
+}
+
+/// Highest base price each fruit is bought back at in a range of offers, to value the fruit held by
+/// the best price it can still be sold for
+struct SalePrices {
+    //Indexed by fruit, offer the range starts at and offer the range ends before
+    best: Vec<Vec<Vec<Option<Money>>>>
+}
+
+impl SalePrices {
+    fn new(offers :&[FruitOffer]) -> SalePrices {
+        let best = Fruit::iter().map(|fruit| {
+            (0..=offers.len()).map(|from| {
+                let mut best = None;
+                let mut until = vec![None; from + 1];
+                for offer in &offers[from..] {
+                    if offer.get_fruit() == fruit && Auction::new(offer).is_none() {
+                        best = best.max(Some(offer.get_resale_base_price()));
+                    }
+                    until.push(best);
+                }
+                until
+            }).collect()
+        }).collect();
+        return SalePrices { best };
+    }
+
+    /// Most one of the stock's fruit sells for after the offer with id offer_id and before it
+    /// spoils, nothing if it can not be sold
+    fn best_sale(&self, stock :&Stock, offer_id :u32) -> Money {
+        let offers = self.best[0].len() - 1;
+        let from = (offer_id as usize + 1).min(offers);
+        let until = (stock.spoils_at as usize).clamp(from, offers);
+        return match self.best[stock.fruit as usize][from][until] {
+            None => { Money::from_cents(0) }
+            Some(base_price) => { stock.quality.sell_price(base_price) }
+        };
+    }
+
+    /// Cash and what the inventory sells for at the best prices offered after the offer with id
+    /// offer_id and before the fruit spoils
+    fn worth(&self, inventory :&[Stock], cash :Money, offer_id :u32) -> Money {
+        return inventory.iter().fold(cash, |worth, stock| {
+            let sale = self.best_sale(stock, offer_id).saturating_mul(stock.count);
+            worth.checked_add(sale).unwrap_or(worth)
+        });
+    }
+}
+
+/// Solver thread searching for the optimal score of a game while it is played, see solve. The
+/// search is canceled and the thread joined when the handler is stopped or dropped, so the thread
+/// does not outlive the game whichever way the game ends
+pub(crate) struct SolverThreadHandler {
+    cancel: Arc<AtomicBool>,
+    solution: Receiver<Solution>,
+    thread: Option<JoinHandle<()>>
+}
+
+impl SolverThreadHandler {
+    /// Start a new thread searching for the optimal score of the game played with seed
+    pub(crate) fn new(seed :u64) -> SolverThreadHandler {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let cancel_2 = Arc::clone(&cancel);
+        let (send_solution, solution) = mpsc::channel();
+        let thread = thread::spawn(move || {
+            if let Some(optimal) = solve_offers(seed, &offers_for_seed(seed), &cancel_2) {
+                let _ = send_solution.send(optimal);
+            }
+        });
+        return SolverThreadHandler { cancel, solution, thread: Some(thread) };
+    }
+
+    /// Waits up to timeout for the search to end. Returns None if it has not ended yet
+    pub(crate) fn wait(&mut self, timeout :Duration) -> Option<Solution> {
+        let optimal = self.solution.recv_timeout(timeout).ok()?;
+        self.stop();
+        return Some(optimal);
+    }
+
+    /// Cancels the search and waits for the thread to end
+    pub(crate) fn stop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for SolverThreadHandler {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Plan of the solution, one line for each offer on which fruit is sold or bought
+impl fmt::Display for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "optimal score {} for seed {}, starting with {}", self.score, self.seed, Config::STARTING_CASH)?;
+        for step in &self.moves {
+            write!(f, "\noffer {:>3}  {:<12} sell {:>2}  buy {:>2}  sell back {:>2}  cash {}",
+                   step.offer_id + 1, step.fruit.get_name(), step.sold, step.bought, step.sold_back, step.cash)?;
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fruit::auction::AuctionLot;
+    use crate::fruit::bulk::{BulkLot, TieredPrice};
+    use crate::player::Player;
+
+    #[test]
+    fn plan_buys_cheap_and_sells_dear() {
+        let mut fruit_store = FruitStore::with_seed(0);
+        let cheap = fruit_store.scripted_offer(Fruit::Apples, Quality::A, Money::from_dollars(1));
+        let dear = fruit_store.scripted_offer(Fruit::Apples, Quality::A, Money::from_dollars(5));
+        let solution = solve_offers(0, &[cheap, dear], &AtomicBool::new(false)).unwrap();
+
+        let price = cheap.get_price();
+        let bought = ((Config::STARTING_CASH.get_cents() / price.get_cents()) as u32).min(Config::STORAGE_CAPACITY);
+        let cash = Config::STARTING_CASH.checked_sub(price.saturating_mul(bought)).unwrap()
+            .checked_add(Quality::A.sell_price(dear.get_resale_base_price()).saturating_mul(bought)).unwrap();
+        assert_eq!(solution.score, cash);
+        assert_eq!(solution.moves.len(), 2);
+        assert_eq!((solution.moves[0].sold, solution.moves[0].bought), (0, bought));
+        assert_eq!((solution.moves[1].sold, solution.moves[1].bought), (bought, 0));
+    }
+
+    #[test]
+    fn plan_without_a_profit_keeps_the_starting_cash() {
+        let mut fruit_store = FruitStore::with_seed(0);
+        let offers = [
+            fruit_store.scripted_offer(Fruit::Banana, Quality::B, Money::from_dollars(3)),
+            fruit_store.scripted_offer(Fruit::Banana, Quality::B, Money::from_dollars(3))
+        ];
+        let solution = solve_offers(0, &offers, &AtomicBool::new(false)).unwrap();
+        assert_eq!(solution.score, Config::STARTING_CASH);
+        assert!(solution.moves.is_empty());
+    }
+
+    #[test]
+    fn canceled_search_finds_nothing() {
+        assert!(solve_offers(1, &offers_for_seed(1), &AtomicBool::new(true)).is_none());
+        let mut solving = SolverThreadHandler::new(1);
+        solving.stop();
+        assert!(solving.thread.is_none());
+        assert!(solving.wait(Duration::from_millis(0)).is_none());
+    }
+
+    /// Offers of every kind, priced so that a few fruit fill up the starting cash
+    fn market() -> Vec<FruitOffer> {
+        let dollars = Money::from_dollars;
+        let offer = |id, fruit, price, base_price, kind| FruitOffer::scripted(id, fruit, Quality::A, price, base_price, kind);
+        let tiers = TieredPrice { threshold: 1, discounted_price: dollars(28) };
+        let bulk = BulkLot { lot_size: 2 };
+        let auction = AuctionLot { lot_size: 2, increment: dollars(2), rival_limit: dollars(25) };
+        return vec![
+            offer(0, Fruit::Apples, dollars(40), dollars(25), OfferKind::Tiered(tiers)),
+            offer(1, Fruit::Banana, Money::from_cents(3840), dollars(15), OfferKind::Bulk(bulk)),
+            offer(2, Fruit::Coconut, dollars(20), dollars(20), OfferKind::Auction(auction)),
+            offer(3, Fruit::Apples, dollars(64), dollars(40), OfferKind::Fixed),
+            offer(4, Fruit::Banana, dollars(48), dollars(30), OfferKind::Fixed),
+            offer(5, Fruit::Coconut, dollars(40), dollars(25), OfferKind::Fixed)
+        ];
+    }
+
+    /// What the player is left with after the sales and purchases, None if the game refuses one.
+    /// Actions are a purchase of the offer's lot if true, a sale if false, and None moves on to the
+    /// next offer. Auctions are won with the lowest winning bid
+    fn replay(offers :&[FruitOffer], actions :&[Option<bool>]) -> Option<(Player, usize)> {
+        let mut player = Player::new(Config::STARTING_CASH);
+        let (mut index, mut bought) = (0, 0);
+        for action in actions {
+            let offer = offers.get(index)?;
+            match action {
+                None => {
+                    index += 1;
+                    bought = 0;
+                    player.remove_spoiled(index as u32);
+                }
+                Some(true) => {
+                    let price = match Auction::new(offer) {
+                        Some(_) if bought > 0 => { return None; }
+                        Some(auction) => { auction.get_lowest_winning_bid() }
+                        None => { offer.get_price_after(bought) }
+                    };
+                    let discounted = offer.is_discounted_after(bought);
+                    player.buy_lot(offer.get_fruit(), offer.get_quality(), offer.get_units(), price, offer.get_id(), discounted).ok()?;
+                    bought += offer.get_units();
+                }
+                Some(false) => {
+                    Auction::new(offer).is_none().then_some(())?;
+                    player.sell_offer(offer).ok()?;
+                }
+            }
+        }
+        return Some((player, index));
+    }
+
+    /// Most cash any sequence of sales and purchases on the offers ends with
+    fn most_cash(offers :&[FruitOffer], actions :&mut Vec<Option<bool>>) -> Option<Money> {
+        let (player, index) = replay(offers, actions)?;
+        if index == offers.len() {
+            return Some(player.get_cash());
+        }
+        let mut most = None;
+        for &action in &[None, Some(true), Some(false)] {
+            actions.push(action);
+            most = most.max(most_cash(offers, actions));
+            actions.pop();
+        }
+        return most;
+    }
+
+    #[test]
+    fn plan_ends_with_the_most_cash_of_any_play() {
+        let offers = market();
+        let solution = solve_offers(0, &offers, &AtomicBool::new(false)).unwrap();
+        assert_eq!(Some(solution.score), most_cash(&offers, &mut Vec::new()));
+    }
+
+    #[test]
+    fn plan_replays_to_its_score() {
+        let mut fruit_store = FruitStore::with_seed(0);
+        let cheap = fruit_store.scripted_offer(Fruit::Apples, Quality::A, Money::from_dollars(1));
+        let dear = fruit_store.scripted_offer(Fruit::Apples, Quality::A, Money::from_dollars(5));
+        for offers in &[market(), vec![cheap, dear]] {
+            let solution = solve_offers(0, offers, &AtomicBool::new(false)).unwrap();
+            let mut actions = Vec::new();
+            let mut moves = solution.moves.iter().peekable();
+            for offer in offers {
+                if let Some(step) = moves.next_if(|step| step.offer_id == offer.get_id()) {
+                    actions.resize(actions.len() + step.sold as usize, Some(false));
+                    let mut sold_back = step.sold_back;
+                    for _ in 0..step.bought / offer.get_units() {
+                        let units = sold_back.min(offer.get_units());
+                        actions.push(Some(true));
+                        actions.resize(actions.len() + units as usize, Some(false));
+                        sold_back -= units;
+                    }
+                    let (player, _) = replay(offers, &actions).unwrap();
+                    assert_eq!(player.get_cash(), step.cash, "offer {}", offer.get_id());
+                }
+                actions.push(None);
+            }
+            assert!(moves.next().is_none());
+            assert_eq!(replay(offers, &actions).unwrap().0.get_cash(), solution.score);
+        }
+    }
+}
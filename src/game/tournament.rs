@@ -0,0 +1,170 @@
+use std::fmt;
+use std::io::{self, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+use rand::{thread_rng, Rng};
+
+use crate::bot;
+use crate::game::error::GameError;
+use crate::game::simulation::{self, ScoreSummary};
+
+/// Every built-in bot played on the same games, so no bot is favoured by an easier market, ranked
+/// by their mean score with the best first
+pub(crate) struct Leaderboard {
+    first_seed: u64,
+    games: u32,
+    /// Seconds since the unix epoch when the tournament was played, to track results over time
+    played_at: u64,
+    ranking: Vec<ScoreSummary>
+}
+
+impl Leaderboard {
+    /// Play games with every bot in bot::NAMES, one game for each seed counting up from seed, a
+    /// random seed when None
+    pub(crate) fn play(games :u32, seed :Option<u64>) -> Result<Leaderboard, GameError> {
+        let first_seed = seed.unwrap_or_else(|| thread_rng().gen());
+        let mut ranking = Vec::with_capacity(bot::NAMES.len());
+        for name in bot::NAMES.iter() {
+            let scores = simulation::scores(name, games, first_seed)?;
+            ranking.push(ScoreSummary::new(name, first_seed, &scores));
+        }
+        let played_at = SystemTime::now().duration_since(UNIX_EPOCH).map(|since| since.as_secs()).unwrap_or(0);
+        return Ok(Leaderboard::new(first_seed, games, played_at, ranking));
+    }
+
+    /// Leaderboard of the bots' scores, ranked by their mean score. Bots with the same mean keep
+    /// their order
+    fn new(first_seed :u64, games :u32, played_at :u64, mut ranking :Vec<ScoreSummary>) -> Leaderboard {
+        ranking.sort_by(|a, b| b.mean.total_cmp(&a.mean));
+        return Leaderboard { first_seed, games, played_at, ranking };
+    }
+
+    /// Write the leaderboard as CSV with a header row, one row for each bot with the best first.
+    /// Money is written with two decimals, bot names are quoted if they hold a comma, quote or line
+    /// break
+    pub(crate) fn write_csv(&self, out :&mut impl Write) -> io::Result<()> {
+        writeln!(out, "played_at,first_seed,games,rank,bot,mean,ci_low,ci_high,min,p10,p25,median,p75,p90,max,losses,bankruptcies")?;
+        for (rank, summary) in self.ranking.iter().enumerate() {
+            let (low, high) = summary.confidence_interval();
+            writeln!(out, "{},{},{},{},{},{:.2},{:.2},{:.2},{},{},{},{},{},{},{},{},{}",
+                     self.played_at, self.first_seed, self.games, rank + 1, csv_field(&summary.bot_name),
+                     summary.mean, low, high, summary.min, summary.p10, summary.p25, summary.median,
+                     summary.p75, summary.p90, summary.max, summary.losses, summary.bankruptcies)?;
+        }
+        return Ok(());
+    }
+
+    /// Write the leaderboard as a JSON object holding the tournament's settings and the ranking,
+    /// an array of one object for each bot with the best first. Money is written as numbers
+    pub(crate) fn write_json(&self, out :&mut impl Write) -> io::Result<()> {
+        writeln!(out, "{{")?;
+        writeln!(out, "  \"played_at\": {},", self.played_at)?;
+        writeln!(out, "  \"first_seed\": {},", self.first_seed)?;
+        writeln!(out, "  \"games\": {},", self.games)?;
+        writeln!(out, "  \"config\": {},", json_string(&simulation::describe_config()))?;
+        writeln!(out, "  \"ranking\": [")?;
+        for (rank, summary) in self.ranking.iter().enumerate() {
+            let (low, high) = summary.confidence_interval();
+            let separator = if rank + 1 < self.ranking.len() { "," } else { "" };
+            writeln!(out, "    {{\"rank\": {}, \"bot\": {}, \"mean\": {:.2}, \"ci_low\": {:.2}, \"ci_high\": {:.2}, \"min\": {}, \"p10\": {}, \"p25\": {}, \"median\": {}, \"p75\": {}, \"p90\": {}, \"max\": {}, \"losses\": {}, \"bankruptcies\": {}}}{}",
+                     rank + 1, json_string(&summary.bot_name), summary.mean, low, high, summary.min, summary.p10,
+                     summary.p25, summary.median, summary.p75, summary.p90, summary.max,
+                     summary.losses, summary.bankruptcies, separator)?;
+        }
+        writeln!(out, "  ]")?;
+        return writeln!(out, "}}");
+    }
+}
+
+/// Text as a CSV field, quoted with its quotes doubled if it holds a comma, quote or line break
+fn csv_field(text :&str) -> String {
+    if !text.contains([',', '"', '\n', '\r']) {
+        return String::from(text);
+    }
+    return format!("\"{}\"", text.replace('"', "\"\""));
+}
+
+/// Text as a quoted JSON string, with quotes, backslashes and control characters escaped
+fn json_string(text :&str) -> String {
+    let mut escaped = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => { escaped.push_str("\\\""); }
+            '\\' => { escaped.push_str("\\\\"); }
+            '\n' => { escaped.push_str("\\n"); }
+            _ if c.is_control() => { escaped.push_str(&format!("\\u{:04x}", c as u32)); }
+            _ => { escaped.push(c); }
+        }
+    }
+    escaped.push('"');
+    return escaped;
+}
+
+/// Leaderboard as a table, leading with the Config settings played with
+impl fmt::Display for Leaderboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} games played by each bot, seeds {} to {}",
+                 self.games, self.first_seed, self.first_seed.wrapping_add(self.games.saturating_sub(1) as u64))?;
+        writeln!(f, "config: {}", simulation::describe_config())?;
+        write!(f, "{:>4}  {:<10} {:>8}  {:>19}  {:>8}  {:>7}  {:>12}", "rank", "bot", "mean", "95% CI", "median", "losses", "bankruptcies")?;
+        for (rank, summary) in self.ranking.iter().enumerate() {
+            let (low, high) = summary.confidence_interval();
+            write!(f, "\n{:>4}  {:<10} {:>8.2}  {:>19}  {:>8}  {:>6.1}%  {:>11.1}%",
+                   rank + 1, summary.bot_name, summary.mean, format!("{:.2} to {:.2}", low, high),
+                   summary.median.to_string(), summary.share(summary.losses), summary.share(summary.bankruptcies))?;
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::money::Money;
+
+    /// Leaderboard of a bot named with a comma and quotes scoring 40 and 60, and a bot scoring 70
+    /// twice listed after it
+    fn leaderboard() -> Leaderboard {
+        let scores = |amounts :&[u64]| -> Vec<Money> { amounts.iter().map(|amount| Money::from_dollars(*amount)).collect() };
+        return Leaderboard::new(3, 2, 1700000000, vec![
+            ScoreSummary::new("mid, \"safe\"", 3, &scores(&[40, 60])),
+            ScoreSummary::new("best", 3, &scores(&[70, 70]))]);
+    }
+
+    fn written(write :impl Fn(&Leaderboard, &mut Vec<u8>) -> io::Result<()>) -> String {
+        let mut out = Vec::new();
+        write(&leaderboard(), &mut out).unwrap();
+        return String::from_utf8(out).unwrap();
+    }
+
+    #[test]
+    fn csv_ranks_the_best_mean_first_and_quotes_names() {
+        assert_eq!(written(|leaderboard, out| leaderboard.write_csv(out)), "\
+played_at,first_seed,games,rank,bot,mean,ci_low,ci_high,min,p10,p25,median,p75,p90,max,losses,bankruptcies
+1700000000,3,2,1,best,70.00,70.00,70.00,70.00,70.00,70.00,70.00,70.00,70.00,70.00,0,0
+1700000000,3,2,2,\"mid, \"\"safe\"\"\",50.00,30.40,69.60,40.00,40.00,40.00,40.00,60.00,60.00,60.00,1,0
+");
+    }
+
+    #[test]
+    fn json_ranks_the_best_mean_first_and_escapes_names() {
+        let expected = format!("{{
+  \"played_at\": 1700000000,
+  \"first_seed\": 3,
+  \"games\": 2,
+  \"config\": \"{}\",
+  \"ranking\": [
+    {{\"rank\": 1, \"bot\": \"best\", \"mean\": 70.00, \"ci_low\": 70.00, \"ci_high\": 70.00, \"min\": 70.00, \"p10\": 70.00, \"p25\": 70.00, \"median\": 70.00, \"p75\": 70.00, \"p90\": 70.00, \"max\": 70.00, \"losses\": 0, \"bankruptcies\": 0}},
+    {{\"rank\": 2, \"bot\": \"mid, \\\"safe\\\"\", \"mean\": 50.00, \"ci_low\": 30.40, \"ci_high\": 69.60, \"min\": 40.00, \"p10\": 40.00, \"p25\": 40.00, \"median\": 40.00, \"p75\": 60.00, \"p90\": 60.00, \"max\": 60.00, \"losses\": 1, \"bankruptcies\": 0}}
+  ]
+}}
+", simulation::describe_config());
+        assert_eq!(written(|leaderboard, out| leaderboard.write_json(out)), expected);
+    }
+
+    #[test]
+    fn fields_are_escaped() {
+        assert_eq!(csv_field("midpoint"), "midpoint");
+        assert_eq!(csv_field("a\nb"), "\"a\nb\"");
+        assert_eq!(json_string("back\\slash\ttab\nline"), "\"back\\\\slash\\u0009tab\\nline\"");
+    }
+}
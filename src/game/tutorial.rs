@@ -0,0 +1,96 @@
+use crate::console::input::PlayerInteractions;
+use crate::config::Config;
+use crate::fruit::Fruit;
+use crate::fruit::quality::Quality;
+use crate::game::money::Money;
+
+/// Single step of the tutorial, the step's offer is shown until the player performs the
+/// interaction the prompt asks for. The prompt is found under the step's name in the message
+/// catalogs' [tutorial] section, it names the interaction's key where it says {key} and the price
+/// the store pays for the offered fruit where it says {price}
+pub(crate) struct TutorialStep {
+    pub(crate) name: &'static str,
+    pub(crate) interaction: PlayerInteractions,
+    pub(crate) fruit: Fruit,
+    pub(crate) quality: Quality,
+    pub(crate) base_price: Money
+}
+
+/// Guided game for new players with scripted offers, see Config::tutorial_steps. Each step moves
+/// on to the next once the player performed the interaction it asks for, an offer which expires
+/// before that is offered again
+pub(crate) struct Tutorial {
+    steps: Vec<TutorialStep>,
+    current: usize,
+    completed: bool
+}
+
+impl Tutorial {
+    pub(crate) fn new() -> Tutorial {
+        return Tutorial { steps: Config::tutorial_steps(), current: 0, completed: false };
+    }
+
+    /// Step whose offer should be shown next, moving on from the current step if it was completed.
+    /// Returns None when all steps have been completed
+    pub(crate) fn next_step(&mut self) -> Option<&TutorialStep> {
+        if self.completed {
+            self.current += 1;
+            self.completed = false;
+        }
+        return self.steps.get(self.current);
+    }
+
+    /// Completes the current step if it asked for the interaction, returns whether it did
+    pub(crate) fn complete(&mut self, interaction :&PlayerInteractions) -> bool {
+        let asked = matches!(self.steps.get(self.current), Some(step) if step.interaction == *interaction);
+        if asked && !self.completed {
+            self.completed = true;
+            return true;
+        }
+        return false;
+    }
+
+    /// Steps left after the current one
+    pub(crate) fn steps_left(&self) -> u32 {
+        return self.steps.len().saturating_sub(self.current + 1) as u32;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_follow_in_order_as_they_are_completed() {
+        let mut tutorial = Tutorial::new();
+        let mut names = Vec::new();
+        while let Some(step) = tutorial.next_step() {
+            let (name, interaction) = (step.name, step.interaction.clone());
+            assert_eq!(tutorial.steps_left() as usize, Config::tutorial_steps().len() - names.len() - 1);
+            names.push(name);
+            assert!(tutorial.complete(&interaction));
+        }
+        assert_eq!(names, vec!["welcome", "info", "sell", "skip", "end"]);
+        assert_eq!(tutorial.steps_left(), 0);
+    }
+
+    #[test]
+    fn step_not_completed_is_offered_again() {
+        let mut tutorial = Tutorial::new();
+        assert_eq!(tutorial.next_step().unwrap().name, "welcome");
+        assert!(!tutorial.complete(&PlayerInteractions::Sell));
+        assert_eq!(tutorial.next_step().unwrap().name, "welcome");
+        assert!(tutorial.complete(&PlayerInteractions::Buy));
+        assert_eq!(tutorial.next_step().unwrap().name, "info");
+    }
+
+    #[test]
+    fn step_is_completed_once() {
+        let mut tutorial = Tutorial::new();
+        tutorial.next_step();
+        assert!(tutorial.complete(&PlayerInteractions::Buy));
+        assert!(!tutorial.complete(&PlayerInteractions::Buy));
+        //Completing twice does not skip the step after it
+        assert_eq!(tutorial.next_step().unwrap().name, "info");
+    }
+}
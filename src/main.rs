@@ -1,140 +1,127 @@
+#![allow(clippy::needless_return)]
+//is_multiple_of is newer than the compilers the game builds with
+#![allow(clippy::manual_is_multiple_of)]
+
+mod bot;
 mod console;
 mod fruit;
 mod player;
 mod config;
+mod game;
 
-use std::sync::{mpsc, Arc};
-use std::{thread};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::ops::Range;
-use std::time::Duration;
+use std::env;
+use std::fs::File;
+use std::process;
+use std::sync::mpsc;
 
+use crate::bot::midpoint::Midpoint;
+use crate::config::Config;
+use crate::config::args::Args;
+use crate::config::file::ConfigFile;
+use crate::console::accessibility::Accessibility;
+use crate::console::key_map::KeyMap;
+use crate::console::locale::Locale;
+use crate::console::theme::Theme;
 use crate::console::output::{Output};
-use crate::console::input::{PlayerInteractionThreadHandler, PlayerInteractions};
-use crate::console::key_handling::KeyHeldController;
-use crate::player::Player;
+use crate::console::input::PlayerInteractionThreadHandler;
+use crate::console::terminal::TerminalGuard;
 use crate::fruit::fruit_store;
-use crate::fruit::fruit_offer_controller::FruitOfferController;
-use crate::config::Config;
+use crate::game::action::ShownOffer;
+use crate::game::advisor::Advisor;
+use crate::game::error::GameError;
+use crate::game::event_loop::EventLoop;
+use crate::game::signals::SignalThreadHandler;
+use crate::game::simulation;
+use crate::game::solver;
+use crate::game::tournament::Leaderboard;
+use crate::game::tutorial::Tutorial;
 
 fn main() {
-    //Used to communicate towards fruit store thread if we wish for the game to be running
-    let game_running: Arc<AtomicBool> = Arc::new(AtomicBool::new(true));
-    //Used to communicate towards fruit store thread if next turn is requested by player
-    let next_turn_requested: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
+    //Errors are printed once the game is over and the terminal has been restored
+    if let Err(error) = run() {
+        eprintln!("fruitstore: {}", error);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), GameError> {
+    let args = Args::parse(env::args().skip(1))?;
+    if args.help {
+        println!("{}", Args::USAGE);
+        return Ok(());
+    }
+    //Simulated games, tournaments and solutions are played without a terminal, only their report is printed
+    if args.simulate {
+        let bot = args.bot.as_deref().unwrap_or(Midpoint::NAME);
+        println!("{}", simulation::simulate(bot, args.games.unwrap_or(Config::SIMULATION_GAMES), args.seed)?);
+        return Ok(());
+    }
+    if args.solve {
+        println!("{}", solver::solve(args.seed.unwrap_or_else(|| fruit_store::FruitStore::new().get_seed())));
+        return Ok(());
+    }
+    if args.tournament {
+        let leaderboard = Leaderboard::play(args.games.unwrap_or(Config::SIMULATION_GAMES), args.seed)?;
+        println!("{}", leaderboard);
+        if let Some(path) = &args.csv {
+            File::create(path).and_then(|mut file| leaderboard.write_csv(&mut file))?;
+        }
+        if let Some(path) = &args.json {
+            File::create(path).and_then(|mut file| leaderboard.write_json(&mut file))?;
+        }
+        return Ok(());
+    }
+
+    //Settings are read before touching the terminal so config errors are printed as usual
+    let config_file = ConfigFile::load()?;
+    let key_map = KeyMap::from_config(&config_file)?;
+    let theme = Theme::from_config(&config_file)?;
+    let locale = Locale::load(args.lang.as_deref(), &config_file)?;
+    let mut accessibility = Accessibility::from_config(&config_file)?;
+    accessibility.screen_reader |= args.screen_reader;
+
+    //Setup fruit store, creating a new offer each time the game loop asks for one
+    let fruit_store = match args.seed {
+        Some(seed) => { fruit_store::FruitStore::with_seed(seed) }
+        None => { fruit_store::FruitStore::new() }
+    };
+
+    //A bot plays in place of the player, seeded like the fruit store so a game can be replayed
+    let bot = match &args.bot {
+        Some(name) => { Some(bot::by_name(name, fruit_store.get_seed())?) }
+        None => { None }
+    };
+
+    //Restores the terminal when the game ends, even when it panics
+    let _terminal_guard = TerminalGuard::new();
 
     //Setup up our console output and print intro
-    let mut output = Output::new();
-    output.print_intro();
+    let mut output = Output::new(key_map.clone(), theme, locale, accessibility.announcements())?;
+    output.print_intro()?;
 
-    //Setup fruit store and mpsc channels used to retrieve new offers from the fruit store.
-    //Offers time out, which is the reason for the fruit store running on separate thread.
+    //Game events such as key presses are sent to the game loop over this channel, offer timeouts
+    //and redraws are scheduled by the game loop itself
     let (tx,rx) = mpsc::channel();
-    let game_running_fruit_store = Arc::clone(&game_running);
-    let next_turn_fruit_store = Arc::clone(&next_turn_requested);
-    let mut fruit_store = fruit_store::FruitStore::new();
-    fruit_store.create_fruit_thread(game_running_fruit_store, next_turn_fruit_store, tx);
+
+    //Offer currently shown to the player, keys are stamped with it so the game loop can reject keys
+    //meant for an offer which has already expired
+    let shown_offer = ShownOffer::new();
 
     //Setup player interaction thread, so we can read key by key player's input
-    let mut player_hid = PlayerInteractionThreadHandler::new();
-
-    //Setup fruit offer controller, responsible for holding fruit store's current offer
-    let mut offer_controller = FruitOfferController::new();
-
-    let mut player = Player::new(Config::STARTING_CASH);
-
-    //Key held controller does so if players keeps holding a key to repeatable buy they do not
-    //mistakenly buy the next offer when the key held was started on the previous offer
-    let mut key_held_controller = KeyHeldController::new();
-
-    // loop
-    //  Check fruit store is alive
-    //  check for new offers,
-    //      if new -> then assign fruit offer to offer_controller
-    //      else -> {
-    //          get current offer
-    //          get player interaction (buy, sell, next offer, end)
-    //          perform interaction
-    //      }
-    while fruit_store.is_alive(){
-        let iter = rx.try_iter().next();
-        match iter{
-            None => {
-                if offer_controller.has_offer() {
-                    let offer = offer_controller.get_offer().unwrap();
-                    let interaction_option = player_hid.get_player_interaction();
-                    let elapsed = offer.millis_since_offer();
-
-                    let proc = offer.get_duration_as_percent_of_elapsed(elapsed);
-
-                    output.print_timeout(proc as u32);
-
-                    let interaction = key_held_controller.filter_option_interaction(interaction_option, offer.get_id());
-                    match interaction{
-                        None =>
-                            {
-                                key_held_controller.cancel_if_elapsed();
-                            }
-                        Some(action) => {
-                            match action{
-                                PlayerInteractions::Buy => {
-                                    let result = player.buy_offer(offer.get_fruit(),offer.get_price());
-                                    match result{
-                                        None => {
-                                            output.print_no_offer();
-                                        }
-                                        Some(_) => {
-                                            output.reset_player_feedback();
-                                            output.print_player(&player);
-                                        }
-                                    }
-                                }
-                                PlayerInteractions::Sell => {
-                                    let result = player.sell_offer(offer.get_fruit(),offer.get_price());
-                                    match result{
-                                        None => {
-                                            output.print_no_such_in_inventory();
-                                        }
-                                        Some(_) => {
-                                            output.reset_player_feedback();
-                                            output.print_player(&player);
-                                        }
-                                    }
-                                }
-                                PlayerInteractions::Exit => {
-                                    game_running.swap(false, Ordering::Relaxed);
-                                }
-                                PlayerInteractions::NextOffer => {
-                                    output.print_skipping_turn();
-                                    next_turn_requested.swap(true, Ordering::Relaxed);
-                                }
-                                PlayerInteractions::Info => {
-                                    let range = Config::range_for_fruit(&offer.get_fruit());
-                                    let print_range = Range{ start: range.start, end: range.end-1 };
-                                    output.print_info(&offer.get_fruit(),print_range);
-                                }
-                            }
-                        }
-                    }
-                }
-                thread::sleep(Duration::from_millis(10))
-            }
-            Some(offer) => {
-                &offer_controller.set_offer(&offer);
-                let offer = &offer_controller.get_offer();
-                match offer{
-                    None => {}
-                    Some(offer) => {
-                        output.print_offer(&offer.get_fruit(), &offer.get_price(), &(Config::AMOUNT_OF_OFFERS - &offer.get_id() - 1));
-                        output.print_player(&player);
-                    }
-                }
-            }
-        }
-    }
-    output.print_end(&player);
-    player_hid.stop();
-}
+    let mut player_hid = PlayerInteractionThreadHandler::new(tx.clone(), shown_offer.clone());
 
+    //Setup signal thread, so SIGTERM and SIGHUP end the game the same way as the player ending it
+    let mut signals = SignalThreadHandler::new(tx)?;
 
+    //The tutorial plays scripted offers instead of the fruit store's random ones
+    let tutorial = if args.tutorial { Some(Tutorial::new()) } else { None };
 
+    let mut event_loop = EventLoop::new(rx, output, fruit_store, shown_offer, key_map, tutorial)
+        .with_bot(bot)
+        .with_advisor(Advisor::new(args.ranked))
+        .with_solver(args.optimal);
+    let result = event_loop.run();
+    player_hid.stop();
+    signals.stop();
+    return result;
+}
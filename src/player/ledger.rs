@@ -0,0 +1,78 @@
+use std::io::{self, Write};
+
+use crate::fruit::Fruit;
+use crate::game::money::Money;
+
+/// Whether the player bought fruit from the store or sold fruit to it
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum Side {
+    Buy,
+    Sell
+}
+
+impl Side {
+    pub(crate) fn get_name(&self) -> &'static str {
+        match self{
+            Side::Buy => {"Buy"}
+            Side::Sell => {"Sell"}
+        }
+    }
+}
+
+/// Single purchase or sale made by the player. Price is what was paid or received for the whole
+/// quantity and cash is what the player had left after the transaction
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct Transaction {
+    pub(crate) offer_id: u32,
+    pub(crate) fruit: Fruit,
+    pub(crate) side: Side,
+    pub(crate) quantity: u32,
+    pub(crate) price: Money,
+    pub(crate) cash: Money
+}
+
+/// Write the transactions as CSV with a header row, offers are numbered from 1 as shown in game and
+/// money is written with two decimals
+pub(crate) fn write_csv(transactions :&[Transaction], out :&mut impl Write) -> io::Result<()> {
+    writeln!(out, "offer,fruit,side,quantity,price,cash")?;
+    for transaction in transactions {
+        writeln!(out, "{},{},{},{},{},{}",
+                 transaction.offer_id + 1,
+                 transaction.fruit.get_name(),
+                 transaction.side.get_name(),
+                 transaction.quantity,
+                 transaction.price,
+                 transaction.cash)?;
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fruit::{FruitOffer, OfferKind};
+    use crate::fruit::quality::Quality;
+    use crate::player::Player;
+
+    #[test]
+    fn csv_has_a_header_and_a_row_per_transaction() {
+        let mut player = Player::new(Money::from_dollars(50));
+        player.buy_lot(Fruit::Apples, Quality::A, 6, Money::from_cents(1250), 0, false).unwrap();
+        player.buy_lot(Fruit::DragonFruit, Quality::C, 1, Money::from_cents(305), 4, false).unwrap();
+        let base_price = Money::from_dollars(1);
+        player.sell_offer(&FruitOffer::scripted(11, Fruit::Apples, Quality::B, base_price, base_price, OfferKind::Fixed)).unwrap();
+        let mut out = Vec::new();
+        write_csv(player.get_ledger(), &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "offer,fruit,side,quantity,price,cash\n\
+                                                     1,Apple,Buy,6,12.50,37.50\n\
+                                                     5,DragonFruit,Buy,1,3.05,34.45\n\
+                                                     12,Apple,Sell,1,1.40,35.85\n");
+    }
+
+    #[test]
+    fn csv_of_no_transactions_is_only_the_header() {
+        let mut out = Vec::new();
+        write_csv(&[], &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "offer,fruit,side,quantity,price,cash\n");
+    }
+}
@@ -1,49 +1,122 @@
+pub mod ledger;
+
 use std::collections::HashMap;
 use strum::IntoEnumIterator;
 
-use crate::fruit::Fruit;
+use crate::fruit::{Fruit, FruitOffer};
+use crate::fruit::quality::Quality;
+use crate::config::Config;
+use crate::game::error::GameError;
+use crate::game::money::Money;
+use crate::player::ledger::{Side, Transaction};
+
+/// A single fruit in the player's inventory, fruit spoils when the offer with id spoils_at arrives.
+/// Fruit bought at a volume discount remembers the offer it was bought on in discounted_on
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct Stock {
+    quality: Quality,
+    spoils_at: u32,
+    discounted_on: Option<u32>
+}
 
+/// Player's cash and inventory, every purchase and sale is recorded in the player's ledger
 pub(crate) struct Player{
-    cash: u32,
-    inventory: HashMap<Fruit,u32>
+    cash: Money,
+    inventory: HashMap<Fruit,Vec<Stock>>,
+    ledger: Vec<Transaction>
 }
 
 impl Player{
-    pub(crate) fn new(starting_cash :u32)->Player{
-        let mut inventory: HashMap<Fruit,u32> = Default::default();
+    pub(crate) fn new(starting_cash :Money)->Player{
+        let mut inventory: HashMap<Fruit,Vec<Stock>> = Default::default();
         for fruit in Fruit::iter() {
-            inventory.insert(fruit,0);
+            inventory.insert(fruit,Vec::new());
         }
-        Player{ cash: starting_cash, inventory }
+        Player{ cash: starting_cash, inventory, ledger: Vec::new() }
     }
 
-    /// increment user inventory of a particular fruit by 1 while decrementing cash accordingly.
-    /// returns None if user does not have enough cash else returns same parameters as given
-    pub(crate) fn buy_offer(&mut self, fruit :Fruit, price :u32) -> Option<(Fruit,u32)> {
-        if price > self.cash{
-            return None;
+    /// increment user inventory of a particular fruit and grade by the lot size while decrementing
+    /// cash by the price of the whole lot. Either the whole lot is bought or nothing is, single
+    /// fruit offers are bought as a lot of 1. Fruit keeps for the shelf life of the grade counted
+    /// from the offer it was bought on. Fruit bought at a volume discount is marked as such, see
+    /// sell_offer.
+    /// returns InsufficientFunds if user does not have enough cash, StorageFull if the lot does not
+    /// fit in storage, else returns fruit and price paid
+    pub(crate) fn buy_lot(&mut self, fruit :Fruit, quality :Quality, lot_size :u32, price :Money, offer_id :u32, discounted :bool) -> Result<(Fruit,Money), GameError> {
+        let cash = self.cash.checked_sub(price).ok_or(GameError::InsufficientFunds)?;
+        if self.get_amount_of_fruits() + lot_size > Config::STORAGE_CAPACITY {
+            return Err(GameError::StorageFull);
+        }
+        self.cash = cash;
+        let spoils_at = offer_id + Config::shelf_life_for_quality(&quality);
+        let discounted_on = if discounted { Some(offer_id) } else { None };
+        let stock = self.inventory.entry(fruit).or_default();
+        for _ in 0..lot_size {
+            stock.push(Stock{ quality, spoils_at, discounted_on });
         }
-        self.cash = self.cash-price;
-        let amount = self.get_amount_of_fruit(fruit);
-        let new_amount = amount+1;
-        self.inventory.insert(fruit,new_amount);
-        return Some((fruit, price));
+        self.record(offer_id, fruit, Side::Buy, lot_size, price);
+        return Ok((fruit, price));
     }
 
-    /// decrement user inventory of a particular fruit by 1 while increasing cash accordingly.
-    /// returns None if user does not have any inventory of the particular fruit
-    pub(crate) fn sell_offer(&mut self, fruit :Fruit, price :u32) -> Option<(Fruit,u32)> {
-        let amount = self.get_amount_of_fruit(fruit);
-        if amount <= 0 {
-            return None;
+    /// decrement user inventory of the offered fruit by 1 while increasing cash accordingly.
+    /// The best graded fruit is sold first, and of those the one closest to spoiling. The store pays
+    /// the base price it buys back at on the offer, see FruitOffer::get_resale_base_price, scaled by
+    /// the resale value of the grade sold. Fruit bought at a volume discount on the offer itself is
+    /// bought back at the discount, see FruitOffer::get_discounted_resale_base_price, and sold last
+    /// of the fruit which is otherwise alike.
+    /// returns NoInventory if user does not have any inventory of the particular fruit, CashOverflow
+    /// if the price received does not fit in the user's cash, else returns fruit and price received
+    pub(crate) fn sell_offer(&mut self, offer :&FruitOffer) -> Result<(Fruit,Money), GameError> {
+        let fruit = offer.get_fruit();
+        let (index, price) = self.next_sale(offer).ok_or(GameError::NoInventory)?;
+        self.cash = self.cash.checked_add(price).ok_or(GameError::CashOverflow)?;
+        self.inventory.entry(fruit).or_default().remove(index);
+        self.record(offer.get_id(), fruit, Side::Sell, 1, price);
+        return Ok((fruit, price));
+    }
+
+    /// Price the store pays for the offered fruit the player would sell next, see sell_offer. None
+    /// if the player holds none of the fruit
+    pub(crate) fn get_sell_price(&self, offer :&FruitOffer) -> Option<Money> {
+        return self.next_sale(offer).map(|(_, price)| price);
+    }
+
+    /// Index in the fruit's stock of the fruit sold next on the offer together with its price
+    fn next_sale(&self, offer :&FruitOffer) -> Option<(usize, Money)> {
+        let discounted = |stock :&Stock| stock.discounted_on == Some(offer.get_id());
+        let (index, stock) = self.inventory.get(&offer.get_fruit())?.iter().enumerate()
+            .min_by_key(|(_, stock)| (stock.quality, stock.spoils_at, discounted(stock)))?;
+        let base_price = if discounted(stock) { offer.get_discounted_resale_base_price() } else { offer.get_resale_base_price() };
+        return Some((index, stock.quality.sell_price(base_price)));
+    }
+
+    /// Add transaction to the ledger, must be called after cash has been updated
+    fn record(&mut self, offer_id :u32, fruit :Fruit, side :Side, quantity :u32, price :Money) {
+        self.ledger.push(Transaction { offer_id, fruit, side, quantity, price, cash: self.cash });
+    }
+
+    /// Removes all fruit which has spoiled by the time the offer with id offer_id arrives.
+    /// Returns the amount of each fruit which spoiled, fruits which did not spoil are left out
+    pub(crate) fn remove_spoiled(&mut self, offer_id :u32) -> Vec<(Fruit,u32)> {
+        let mut spoiled = Vec::new();
+        for fruit in Fruit::iter() {
+            let stock = self.inventory.entry(fruit).or_default();
+            let before = stock.len();
+            stock.retain(|stock| stock.spoils_at > offer_id);
+            let amount = (before - stock.len()) as u32;
+            if amount > 0 {
+                spoiled.push((fruit, amount));
+            }
         }
-        self.cash = self.cash+price;
-        let new_amount = amount-1;
-        self.inventory.insert(fruit,new_amount);
-        return Some((fruit, price));
+        return spoiled;
+    }
+
+    /// Every purchase and sale of the game, oldest first
+    pub(crate) fn get_ledger(&self)->&[Transaction]{
+        return &self.ledger;
     }
 
-    pub(crate) fn get_cash(&self)->u32{
+    pub(crate) fn get_cash(&self)->Money{
         return self.cash;
     }
 
@@ -52,9 +125,127 @@ impl Player{
             None => {
                 0
             }
-            Some(amount) => {
-                *amount
+            Some(stock) => {
+                stock.len() as u32
+            }
+        }
+    }
+
+    /// Amount of fruit in storage, all kinds of fruit counted together
+    pub(crate) fn get_amount_of_fruits(&self)->u32{
+        return self.inventory.values().map(|stock| stock.len() as u32).sum();
+    }
+
+    pub(crate) fn get_amount_of_fruit_quality(&self, fruit:Fruit, quality:Quality)->u32{
+        match self.inventory.get(&fruit) {
+            None => {
+                0
+            }
+            Some(stock) => {
+                stock.iter().filter(|stock| stock.quality == quality).count() as u32
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fruit::{FruitOffer, OfferKind};
+    use crate::fruit::bulk::{BulkLot, TieredPrice};
+
+    fn base_price() -> Money {
+        return Money::from_dollars(10);
+    }
+
+    /// Buy the offer purchases times and sell every fruit bought back on the same offer, returns the
+    /// cash left from starting_cash
+    fn round_trip(offer :&FruitOffer, purchases :u32, starting_cash :Money) -> Money {
+        let mut player = Player::new(starting_cash);
+        let mut units_bought = 0;
+        for _ in 0..purchases {
+            let price = offer.get_price_after(units_bought);
+            player.buy_lot(offer.get_fruit(), offer.get_quality(), offer.get_units(), price, offer.get_id(), offer.is_discounted_after(units_bought)).unwrap();
+            units_bought += offer.get_units();
+        }
+        for _ in 0..units_bought {
+            player.sell_offer(offer).unwrap();
+        }
+        return player.get_cash();
+    }
+
+    #[test]
+    fn selling_back_a_bulk_lot_on_its_offer_loses_money() {
+        let starting_cash = Money::from_dollars(1000);
+        for quality in Quality::iter() {
+            let lot = BulkLot { lot_size: Config::BULK_LOT_MAX };
+            let price = quality.buy_price(base_price()).saturating_mul(lot.lot_size).percent(Config::BULK_PRICE_PERCENT);
+            let offer = FruitOffer::scripted(0, Fruit::Apples, quality, price, base_price(), OfferKind::Bulk(lot));
+            assert!(round_trip(&offer, 1, starting_cash) < starting_cash, "grade {}", quality.get_name());
+        }
+    }
+
+    #[test]
+    fn selling_back_volume_discounted_fruit_on_its_offer_loses_money() {
+        let starting_cash = Money::from_dollars(1000);
+        for quality in Quality::iter() {
+            let price = quality.buy_price(base_price());
+            let tiers = TieredPrice { threshold: Config::TIERED_THRESHOLD, discounted_price: price.percent(Config::TIERED_PRICE_PERCENT) };
+            let offer = FruitOffer::scripted(0, Fruit::Apples, quality, price, base_price(), OfferKind::Tiered(tiers));
+            assert!(round_trip(&offer, Config::TIERED_THRESHOLD + 5, starting_cash) < starting_cash, "grade {}", quality.get_name());
+        }
+    }
+
+    fn tiered_offer(id :u32) -> FruitOffer {
+        let price = Quality::B.buy_price(base_price());
+        let tiers = TieredPrice { threshold: 2, discounted_price: price.percent(Config::TIERED_PRICE_PERCENT) };
+        return FruitOffer::scripted(id, Fruit::Apples, Quality::B, price, base_price(), OfferKind::Tiered(tiers));
+    }
+
+    #[test]
+    fn fruit_held_from_earlier_sells_at_full_price_on_a_tiered_offer() {
+        let mut player = Player::new(Money::from_dollars(100));
+        player.buy_lot(Fruit::Apples, Quality::B, 1, Money::from_dollars(1), 0, false).unwrap();
+        let (_, price) = player.sell_offer(&tiered_offer(1)).unwrap();
+        assert_eq!(price, Quality::B.sell_price(base_price()));
+    }
+
+    #[test]
+    fn fruit_discounted_on_an_earlier_offer_sells_at_full_price() {
+        let mut player = Player::new(Money::from_dollars(100));
+        player.buy_lot(Fruit::Apples, Quality::B, 1, Money::from_dollars(1), 0, true).unwrap();
+        let (_, price) = player.sell_offer(&tiered_offer(1)).unwrap();
+        assert_eq!(price, Quality::B.sell_price(base_price()));
+    }
+
+    #[test]
+    fn fruit_discounted_on_the_offer_sells_back_at_the_discount_last() {
+        let offer = tiered_offer(0);
+        let mut player = Player::new(Money::from_dollars(100));
+        for units_bought in 0..3 {
+            player.buy_lot(Fruit::Apples, Quality::B, 1, offer.get_price_after(units_bought), 0, offer.is_discounted_after(units_bought)).unwrap();
+        }
+        let full = Quality::B.sell_price(base_price());
+        let discounted = Quality::B.sell_price(base_price().percent(Config::TIERED_PRICE_PERCENT));
+        assert_eq!(player.get_sell_price(&offer), Some(full));
+        let prices: Vec<Money> = (0..3).map(|_| player.sell_offer(&offer).unwrap().1).collect();
+        assert_eq!(prices, vec![full, full, discounted]);
+        assert_eq!(player.get_sell_price(&offer), None);
+    }
+
+    #[test]
+    fn best_grade_closest_to_spoiling_is_sold_first() {
+        let mut player = Player::new(Money::from_dollars(100));
+        player.buy_lot(Fruit::Apples, Quality::B, 1, Money::from_dollars(1), 0, false).unwrap();
+        player.buy_lot(Fruit::Apples, Quality::A, 1, Money::from_dollars(1), 2, false).unwrap();
+        player.buy_lot(Fruit::Apples, Quality::A, 1, Money::from_dollars(1), 1, false).unwrap();
+        let offer = FruitOffer::scripted(3, Fruit::Apples, Quality::C, base_price(), base_price(), OfferKind::Fixed);
+        let (_, price) = player.sell_offer(&offer).unwrap();
+        assert_eq!(price, Quality::A.sell_price(base_price()));
+        assert_eq!(player.get_amount_of_fruit_quality(Fruit::Apples, Quality::A), 1);
+        //The grade A fruit bought on offer 1 spoiled first, so the one left spoils later
+        let spoils_at = 1 + Config::shelf_life_for_quality(&Quality::A);
+        player.remove_spoiled(spoils_at);
+        assert_eq!(player.get_amount_of_fruit_quality(Fruit::Apples, Quality::A), 1);
+    }
+}